@@ -0,0 +1,138 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    /// Multi-line picture, e.g. the CRT output of day 10, one string per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::Text(x) => write!(f, "{}", x),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Integers too large for an `i64` are kept as [`Answer::Text`] rather than wrapped around.
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(x: $t) -> Self {
+                match i64::try_from(x) {
+                    Ok(i) => Answer::Int(i),
+                    Err(_) => Answer::Text(x.to_string()),
+                }
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    /// Strings spanning several lines become a [`Answer::Grid`].
+    fn from(x: String) -> Self {
+        match x.contains('\n') {
+            true => Answer::Grid(x.lines().map(String::from).collect()),
+            false => Answer::Text(x),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(x: &str) -> Self {
+        Answer::from(x.to_string())
+    }
+}
+
+/// Integers are written as numbers and both text and grids as strings.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(x) => serializer.serialize_i64(*x),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAnswer {
+    Int(i64),
+    Wide(u64),
+    Text(String),
+}
+
+/// Accepts numbers and strings. Strings are always text, so `"0012"` is not the answer
+/// `12`, and integers too large for an `i64` are kept as [`Answer::Text`].
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match RawAnswer::deserialize(deserializer)? {
+            RawAnswer::Int(x) => Answer::Int(x),
+            RawAnswer::Wide(x) => Answer::from(x),
+            RawAnswer::Text(x) => Answer::from(x),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+
+    #[test]
+    fn from_values() {
+        assert_eq!(Answer::from(24000_u32), Answer::Int(24000));
+        assert_eq!(Answer::from(-3_isize), Answer::Int(-3));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::Text("18446744073709551615".to_string())
+        );
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_string()));
+        assert_eq!(
+            Answer::from("#..\n.#."),
+            Answer::Grid(vec!["#..".to_string(), ".#.".to_string()])
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::Int(29703395016).to_string(), "29703395016");
+        assert_eq!(Answer::from("#..\n.#.").to_string(), "#..\n.#.");
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        for answer in [
+            Answer::Int(68787),
+            Answer::from("RFFFWBPNS"),
+            Answer::from("#..\n.#."),
+            Answer::from(u64::MAX),
+        ] {
+            let serialized = toml::Value::try_from(&answer).unwrap();
+            assert_eq!(serialized.try_into::<Answer>().unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn deserialize_integer_string() {
+        let answer: Answer = toml::Value::String("0012".to_string()).try_into().unwrap();
+        assert_eq!(answer, Answer::Text("0012".to_string()));
+        let answer: Answer = toml::Value::Integer(12).try_into().unwrap();
+        assert_eq!(answer, Answer::Int(12));
+        let answer: Answer = serde_json::from_str("18446744073709551615").unwrap();
+        assert_eq!(answer, Answer::from(u64::MAX));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::answer::Answer;
+use crate::report::DayReport;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Name of the expected-answers file kept in the data directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Error, Debug)]
+pub enum AnswersError {
+    #[error("Could not read answers file {}: {}", .0, .1)]
+    Reading(String, std::io::Error),
+    #[error("Could not parse answers file {}: {}", .0, .1)]
+    Parsing(String, toml::de::Error),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ExpectedAnswers {
+    part_1: Option<Answer>,
+    part_2: Option<Answer>,
+}
+
+/// Outcome of comparing a part's answer with the expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } if expected.is_multiline() => {
+                write!(f, "FAIL, expected:\n{}", expected)
+            }
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Known answers for the inputs in a data directory.
+#[derive(Debug, Clone, Default)]
+pub struct AnswerKey {
+    answers: HashMap<String, ExpectedAnswers>,
+}
+
+impl AnswerKey {
+    /// Read the answers file from a data directory. A missing file gives an empty key, so
+    /// every answer is reported as unknown.
+    pub fn load(data_dir: &str) -> Result<Self, AnswersError> {
+        let path = Path::new(data_dir).join(ANSWERS_FILE);
+        if !path.exists() {
+            return Ok(AnswerKey::default());
+        }
+        let file = path.display().to_string();
+        let contents =
+            fs::read_to_string(&path).map_err(|e| AnswersError::Reading(file.clone(), e))?;
+        Self::from_toml(&contents).map_err(|e| AnswersError::Parsing(file, e))
+    }
+
+    fn from_toml(contents: &str) -> Result<Self, toml::de::Error> {
+        Ok(AnswerKey {
+            answers: toml::from_str(contents)?,
+        })
+    }
+
+    /// Expected answer for one part of an input, given by its file name without the
+    /// extension (e.g. `01` or `10_ex1`).
+    pub fn expected(&self, input: &str, part: u8) -> Option<&Answer> {
+        let answers = self.answers.get(input)?;
+        match part {
+            1 => answers.part_1.as_ref(),
+            2 => answers.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Compare each part's answer with the expected answer and record the verdict.
+    pub fn verify(&self, report: &mut DayReport) {
+        for part in report.parts.iter_mut() {
+            part.verdict = Some(
+                match (self.expected(&report.input, part.part), &part.answer) {
+                    (None, _) => Verdict::Unknown,
+                    (Some(expected), Ok(answer)) if expected == answer => Verdict::Pass,
+                    (Some(expected), _) => Verdict::Fail {
+                        expected: expected.clone(),
+                    },
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::answers::{AnswerKey, Verdict};
+    use crate::solutions::get;
+    use crate::solve;
+
+    const ANSWERS: &str = "
+    [01]
+    part_1 = 24000
+    part_2 = \"45000\"
+
+    [10]
+    part_2 = \"\"\"
+    #..
+    .#.\"\"\"
+    ";
+
+    #[test]
+    fn expected_answers() {
+        let key = AnswerKey::from_toml(ANSWERS).unwrap();
+        assert_eq!(key.expected("01", 1), Some(&Answer::Int(24000)));
+        assert_eq!(key.expected("01", 2), Some(&Answer::from("45000")));
+        assert_eq!(key.expected("10", 1), None);
+        assert_eq!(
+            key.expected("10", 2),
+            Some(&Answer::from("    #..\n    .#."))
+        );
+        assert_eq!(key.expected("02", 1), None);
+    }
+
+    #[test]
+    fn verify_report() {
+        let key = AnswerKey::from_toml("[01]\npart_1 = 3000\npart_2 = 1").unwrap();
+        let mut report = solve(get(1).unwrap(), "1000\n2000\n\n3000");
+        report.input = String::from("01");
+        key.verify(&mut report);
+        assert_eq!(report.part(1).unwrap().verdict, Some(Verdict::Pass));
+        assert_eq!(
+            report.part(2).unwrap().verdict,
+            Some(Verdict::Fail {
+                expected: Answer::Int(1)
+            })
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use crate::data::load_raw;
+use crate::error::AocError;
+use crate::isolate;
+use crate::puzzle::Puzzle;
+use crate::report::Failure;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BenchError {
+    #[error("Could not read baseline {}: {}", .0, .1)]
+    Reading(String, std::io::Error),
+    #[error("Could not write baseline {}: {}", .0, .1)]
+    Writing(String, std::io::Error),
+    #[error("Could not parse baseline {}: {}", .0, .1)]
+    Parsing(String, serde_json::Error),
+    #[error("Number of runs must be at least 1.")]
+    NoRuns,
+}
+
+/// Summary statistics of repeated timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_timings(mut timings: Vec<Duration>) -> Self {
+        timings.sort();
+        let n = timings.len();
+        let median = match n % 2 {
+            0 => (timings[n / 2 - 1] + timings[n / 2]) / 2,
+            _ => timings[n / 2],
+        };
+        Stats {
+            min: timings[0],
+            median,
+            mean: timings.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+/// Timings of one phase of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// Timings of the parser and both parts of one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl DayBench {
+    pub fn phase(&self, phase: Phase) -> &Stats {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Part1 => &self.part_1,
+            Phase::Part2 => &self.part_2,
+        }
+    }
+}
+
+fn time_runs<T>(
+    runs: usize,
+    mut f: impl FnMut() -> Result<T, AocError>,
+) -> Result<Vec<Duration>, AocError> {
+    let mut timings = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f()?;
+        timings.push(start.elapsed());
+    }
+    Ok(timings)
+}
+
+/// Time the parser and each part separately, `runs` times each. The parts are timed on a
+/// single parsed input.
+pub fn bench_day(puzzle: &dyn Puzzle, input_data: &str, runs: usize) -> Result<DayBench, AocError> {
+    if runs == 0 {
+        return Err(BenchError::NoRuns.into());
+    }
+    let parse = time_runs(runs, || puzzle.parse(input_data))?;
+    let input = puzzle.parse(input_data)?;
+    let part_1 = time_runs(runs, || puzzle.part_1(&input))?;
+    let part_2 = time_runs(runs, || puzzle.part_2(&input))?;
+    Ok(DayBench {
+        parse: Stats::from_timings(parse),
+        part_1: Stats::from_timings(part_1),
+        part_2: Stats::from_timings(part_2),
+    })
+}
+
+/// Benchmark results keyed by day, as stored in a baseline file.
+pub type Baseline = BTreeMap<u32, DayBench>;
+
+/// Benchmark results keyed by day, with the failure of each day that could not be timed.
+pub type BenchResults = BTreeMap<u32, Result<DayBench, Failure>>;
+
+/// Benchmark each puzzle on its input in the data directory. A day whose input is missing,
+/// or that fails or panics, is recorded as failed and the other days are still timed.
+pub fn bench_days(
+    data_dir: &str,
+    puzzles: &[&dyn Puzzle],
+    runs: usize,
+) -> Result<BenchResults, AocError> {
+    if runs == 0 {
+        return Err(BenchError::NoRuns.into());
+    }
+    let results = puzzles
+        .iter()
+        .map(|puzzle| {
+            let bench = isolate(|| {
+                let input_data = load_raw(data_dir, puzzle.day(), None)?;
+                bench_day(*puzzle, &input_data, runs)
+            });
+            (puzzle.day(), bench)
+        })
+        .collect();
+    Ok(results)
+}
+
+/// The days that were timed, to save as a baseline.
+pub fn timed_days(results: &BenchResults) -> Baseline {
+    results
+        .iter()
+        .filter_map(|(day, bench)| Some((*day, *bench.as_ref().ok()?)))
+        .collect()
+}
+
+pub fn save_baseline(path: &str, results: &Baseline) -> Result<(), BenchError> {
+    let json = serde_json::to_string_pretty(results)
+        .map_err(|e| BenchError::Writing(path.to_string(), std::io::Error::other(e)))?;
+    fs::write(path, json).map_err(|e| BenchError::Writing(path.to_string(), e))
+}
+
+pub fn load_baseline(path: &str) -> Result<Baseline, BenchError> {
+    let json = fs::read_to_string(path).map_err(|e| BenchError::Reading(path.to_string(), e))?;
+    serde_json::from_str(&json).map_err(|e| BenchError::Parsing(path.to_string(), e))
+}
+
+/// Relative change of the median time against a baseline, e.g. "-12.5%".
+fn change(current: &Stats, baseline: &Stats) -> String {
+    let before = baseline.median.as_secs_f64();
+    if before == 0.0 {
+        return String::from("n/a");
+    }
+    let pct = 100.0 * (current.median.as_secs_f64() - before) / before;
+    format!("{:+.1}%", pct)
+}
+
+/// Table of the benchmark results, with the change in median time against the baseline
+/// when one is given.
+pub fn bench_table(results: &BenchResults, baseline: Option<&Baseline>) -> String {
+    let mut header = format!(
+        "{:>4} | {:<6} | {:>12} | {:>12} | {:>12}",
+        "Day", "Phase", "Min", "Median", "Mean"
+    );
+    if baseline.is_some() {
+        header.push_str(" | vs. baseline");
+    }
+    let mut table = vec![header];
+    for (day, bench) in results.iter() {
+        let bench = match bench {
+            Ok(bench) => bench,
+            Err(failure) => {
+                table.push(format!("{:>4} | failed, {}", day, failure.summary()));
+                continue;
+            }
+        };
+        for phase in Phase::ALL {
+            let stats = bench.phase(phase);
+            let mut row = format!(
+                "{:>4} | {:<6} | {:>12} | {:>12} | {:>12}",
+                day,
+                phase.to_string(),
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean)
+            );
+            if let Some(baseline) = baseline {
+                let vs = match baseline.get(day) {
+                    Some(b) => change(stats, b.phase(phase)),
+                    None => String::from("new"),
+                };
+                row.push_str(&format!(" | {:>12}", vs));
+            }
+            table.push(row);
+        }
+    }
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::{bench_day, bench_days, bench_table, change, timed_days, Stats};
+    use crate::puzzle::Puzzle;
+    use crate::report::Failure;
+    use crate::solutions::get;
+    use crate::tests::Panicky;
+    use std::fs;
+    use std::time::Duration;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_timings(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(3));
+
+        let stats = Stats::from_timings(vec![ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(4));
+    }
+
+    #[test]
+    fn change_against_baseline() {
+        let before = Stats::from_timings(vec![ms(10)]);
+        let after = Stats::from_timings(vec![ms(8)]);
+        assert_eq!(change(&after, &before), "-20.0%");
+    }
+
+    #[test]
+    fn bench_example() {
+        assert!(bench_day(get(1).unwrap(), "1000\n2000\n\n3000", 3).is_ok());
+        assert!(bench_day(get(1).unwrap(), "1000", 0).is_err());
+    }
+
+    #[test]
+    fn failing_days_do_not_stop_the_others() {
+        let dir = std::env::temp_dir().join(format!("aoc-bench-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.txt"), "1000\n2000").unwrap();
+        fs::write(dir.join("99.txt"), "1").unwrap();
+        let puzzles: Vec<&dyn Puzzle> = vec![&Panicky, get(1).unwrap(), get(2).unwrap()];
+        let results = bench_days(dir.to_str().unwrap(), &puzzles, 2).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(results[&99], Err(Failure::Panicked(_))));
+        assert!(matches!(results[&2], Err(Failure::Error(_))));
+        assert_eq!(timed_days(&results).keys().collect::<Vec<_>>(), vec![&1]);
+        let table = bench_table(&results, None);
+        assert!(table.contains("  99 | failed, panicked: part 1 exploded"));
+        assert_eq!(table.lines().filter(|l| l.starts_with("   1 |")).count(), 3);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::answer::Answer;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Could not read cache {}: {}", .0, .1)]
+    Reading(String, std::io::Error),
+    #[error("Could not write cache {}: {}", .0, .1)]
+    Writing(String, std::io::Error),
+    #[error("Could not parse cache {}: {}", .0, .1)]
+    Parsing(String, serde_json::Error),
+}
+
+/// Name of the cache file in the data directory.
+pub const CACHE_FILE: &str = ".answer-cache.json";
+
+/// Hash of an input's contents (64-bit FNV-1a), stable across builds and platforms unlike
+/// the standard library's hasher.
+pub fn input_hash(input_data: &str) -> u64 {
+    input_data.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Answers computed by earlier runs, keyed by day, part, solution version and input hash.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cache {
+    answers: BTreeMap<String, Answer>,
+}
+
+fn key(day: u32, part: u8, version: u32, hash: u64) -> String {
+    format!("day{:02}-part{}-v{}-{:016x}", day, part, version, hash)
+}
+
+impl Cache {
+    /// Load the cache file, or start an empty cache if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, CacheError> {
+        let name = path.display().to_string();
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| CacheError::Parsing(name, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Cache::default()),
+            Err(e) => Err(CacheError::Reading(name, e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CacheError> {
+        let name = path.display().to_string();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| CacheError::Writing(name.clone(), std::io::Error::other(e)))?;
+        fs::write(path, json).map_err(|e| CacheError::Writing(name, e))
+    }
+
+    pub fn get(&self, day: u32, part: u8, version: u32, hash: u64) -> Option<&Answer> {
+        self.answers.get(&key(day, part, version, hash))
+    }
+
+    pub fn insert(&mut self, day: u32, part: u8, version: u32, hash: u64, answer: Answer) {
+        self.answers.insert(key(day, part, version, hash), answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::cache::{input_hash, Cache};
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(input_hash("1000\n2000"), input_hash("1000\n2001"));
+    }
+
+    #[test]
+    fn cache_keys() {
+        let mut cache = Cache::default();
+        cache.insert(1, 1, 1, 42, Answer::Int(24000));
+        assert_eq!(cache.get(1, 1, 1, 42), Some(&Answer::Int(24000)));
+        assert_eq!(cache.get(1, 2, 1, 42), None);
+        assert_eq!(cache.get(1, 1, 2, 42), None);
+        assert_eq!(cache.get(1, 1, 1, 43), None);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-cache-{}.json", uuid::Uuid::new_v4()));
+        let mut cache = Cache::load(&path).unwrap();
+        assert_eq!(cache, Cache::default());
+        cache.insert(10, 2, 1, 7, Answer::from("#..\n.#."));
+        cache.save(&path).unwrap();
+        assert_eq!(Cache::load(&path).unwrap(), cache);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
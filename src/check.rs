@@ -0,0 +1,367 @@
+//! Differential checking of the solutions against the reference solutions on generated
+//! inputs. The first input they disagree on is shrunk to as few blank-line separated blocks,
+//! lines and characters as still make them disagree.
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::puzzle::Puzzle;
+use crate::reference::{self, Reference};
+use crate::report::Failure;
+use crate::{inputgen, isolate_with_limit, solutions, solve_with_limit};
+use std::fmt;
+use std::ops::Range;
+use std::time::Duration;
+
+/// Time limit for the reference and the solution on each input while checking, so that a
+/// step that hangs on some input does not stall the check.
+pub const STEP_LIMIT: Duration = Duration::from_secs(10);
+
+/// An input on which a solution does not give the reference answer, or on which the
+/// reference itself panics or times out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u32,
+    /// Seed of the generated input the disagreement was found on.
+    pub seed: u64,
+    pub part: u8,
+    /// Answer of the reference solution on the minimized input, or why it did not give one.
+    pub expected: Result<Answer, Failure>,
+    /// Answer of the solution on the minimized input, or why it did not give one.
+    pub actual: Result<Answer, Failure>,
+    /// The minimized input.
+    pub input: String,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let multiline = |text: String| match text.contains('\n') {
+            true => format!("\n{}", text),
+            false => format!(" {}", text),
+        };
+        match &self.expected {
+            Ok(expected) => {
+                writeln!(
+                    f,
+                    "Day {} puzzle {} disagrees with the reference on the input from seed {}.",
+                    self.day, self.part, self.seed
+                )?;
+                writeln!(f, "Expected:{}", multiline(expected.to_string()))?;
+            }
+            Err(failure) => {
+                writeln!(
+                    f,
+                    "The reference of day {} puzzle {} failed on the input from seed {}.",
+                    self.day, self.part, self.seed
+                )?;
+                writeln!(f, "Reference: {}", failure)?;
+            }
+        }
+        match &self.actual {
+            Ok(answer) => writeln!(f, "Got:{}", multiline(answer.to_string()))?,
+            Err(failure) => writeln!(f, "Got: {}", failure)?,
+        }
+        write!(f, "Minimized input:\n{}", self.input)
+    }
+}
+
+/// What the reference and the solution gave on the first part that does not agree.
+type Outcome = (u8, Result<Answer, Failure>, Result<Answer, Failure>);
+
+/// Run the reference for a part on its own thread, so that a panic or a hang is reported
+/// instead of stopping the check.
+fn run_reference(
+    reference: Reference,
+    input: &str,
+    part: u8,
+    limit: Duration,
+) -> Result<Option<Answer>, Failure> {
+    let input = input.to_string();
+    isolate_with_limit(Some(limit), move || Ok(reference(&input, part)))
+}
+
+/// The first part on which the solution does not give the reference answer, or on which the
+/// reference fails, with what both gave. Parts the reference has no answer for are skipped.
+fn compare(
+    puzzle: &'static dyn Puzzle,
+    reference: Reference,
+    input: &str,
+    parts: &[u8],
+    limit: Duration,
+) -> Option<Outcome> {
+    if input.trim().is_empty() {
+        return None;
+    }
+    let expected = parts
+        .iter()
+        .filter_map(|part| match run_reference(reference, input, *part, limit) {
+            Ok(answer) => answer.map(|answer| (*part, Ok(answer))),
+            Err(failure) => Some((*part, Err(failure))),
+        })
+        .collect::<Vec<_>>();
+    if expected.is_empty() {
+        return None;
+    }
+    let report = solve_with_limit(puzzle, input, Some(limit));
+    for (part, expected) in expected {
+        let actual = match (&report.error, report.part(part)) {
+            (Some(e), _) => Err(e.clone()),
+            (None, Some(p)) => p.answer.clone(),
+            (None, None) => continue,
+        };
+        if expected.is_err() || actual != expected {
+            return Some((part, expected, actual));
+        }
+    }
+    None
+}
+
+/// Remove runs of units, halving the run length down to single units, as long as what is
+/// left still `fails`.
+fn shrink(
+    mut units: Vec<String>,
+    join: impl Fn(&[String]) -> String,
+    fails: &impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut run = (units.len() / 2).max(1);
+    loop {
+        let mut removed = false;
+        let mut i = 0;
+        while i < units.len() {
+            let end = (i + run).min(units.len());
+            let candidate = [&units[..i], &units[end..]].concat();
+            if !candidate.is_empty() && fails(&join(&candidate)) {
+                units = candidate;
+                removed = true;
+            } else {
+                i += run;
+            }
+        }
+        if (run == 1) & !removed {
+            return units;
+        }
+        run = (run / 2).max(1);
+    }
+}
+
+/// Shrink an input as long as it still `fails`: first by whole blocks separated by blank
+/// lines, then by lines, then by characters within each line.
+pub fn minimize(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let lines_of = |text: &str| text.lines().map(String::from).collect::<Vec<_>>();
+    let blocks = input
+        .trim_end()
+        .split("\n\n")
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let blocks = shrink(blocks, |b| b.join("\n\n") + "\n", &fails);
+
+    let mut lines = shrink(
+        lines_of(&blocks.join("\n\n")),
+        |l| l.join("\n") + "\n",
+        &fails,
+    );
+    for i in 0..lines.len() {
+        let chars = lines[i].chars().map(String::from).collect::<Vec<_>>();
+        let with_line = |chars: &[String]| {
+            let mut candidate = lines.clone();
+            candidate[i] = chars.concat();
+            candidate.join("\n") + "\n"
+        };
+        let shrunk = shrink(chars, with_line, &fails).concat();
+        lines[i] = shrunk;
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Run the solution and the reference on the input generated from each seed in turn, each
+/// step within `limit`, and return the first disagreement with its input minimized. While
+/// minimizing, an input only counts if the reference fails on it exactly when it failed on
+/// the original input.
+pub fn check(
+    day: u32,
+    puzzle: &'static dyn Puzzle,
+    reference: Reference,
+    generate: impl Fn(u64) -> String,
+    seeds: Range<u64>,
+    parts: &[u8],
+    limit: Duration,
+) -> Option<Disagreement> {
+    for seed in seeds {
+        let input = generate(seed);
+        let Some((part, expected, _)) = compare(puzzle, reference, &input, parts, limit) else {
+            continue;
+        };
+        let reference_failed = expected.is_err();
+        let input = minimize(&input, |candidate| {
+            matches!(
+                compare(puzzle, reference, candidate, &[part], limit),
+                Some((p, e, _)) if p == part && e.is_err() == reference_failed
+            )
+        });
+        let (_, expected, actual) = compare(puzzle, reference, &input, &[part], limit)?;
+        return Some(Disagreement {
+            day,
+            seed,
+            part,
+            expected,
+            actual,
+            input,
+        });
+    }
+    None
+}
+
+/// Check a registered day against its reference solution on the inputs generated from the
+/// seeds, for one part or both. Days without a reference solution or an input generator,
+/// such as newly added ones, cannot be checked.
+pub fn check_day(
+    day: u32,
+    seeds: Range<u64>,
+    part: Option<u8>,
+) -> Result<Option<Disagreement>, AocError> {
+    let puzzle = solutions::get(day).ok_or(AocError::UnknownDay(day))?;
+    let reference = reference::get(day).ok_or(AocError::NoReference(day))?;
+    if !inputgen::days().contains(&day) {
+        return Err(AocError::NoGenerator(day));
+    }
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    Ok(check(
+        day,
+        puzzle,
+        reference,
+        |seed| inputgen::generate(day, seed).unwrap_or_default(),
+        seeds,
+        &parts,
+        STEP_LIMIT,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::check::{check, check_day, minimize, STEP_LIMIT};
+    use crate::error::AocError;
+    use crate::inputgen;
+    use crate::puzzle::Solution;
+    use crate::report::Failure;
+    use crate::solutions::DAYS;
+    use crate::tests::time_limits;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Counts lines, but one too many if a line has a 7 in it.
+    struct Miscount;
+
+    impl Solution for Miscount {
+        const DAY: u32 = 97;
+        const TITLE: &'static str = "Miscount";
+
+        type Input = Vec<String>;
+        type Output1 = usize;
+        type Output2 = usize;
+        type Error = AocError;
+
+        fn parse(input_data: &str) -> Result<Vec<String>, AocError> {
+            Ok(input_data.trim().lines().map(String::from).collect())
+        }
+
+        fn part_1(lines: &Vec<String>) -> Result<usize, AocError> {
+            Ok(lines.len() + lines.iter().any(|l| l.contains('7')) as usize)
+        }
+
+        fn part_2(_: &Vec<String>) -> Result<usize, AocError> {
+            Ok(0)
+        }
+    }
+
+    fn count_lines(input: &str, part: u8) -> Option<Answer> {
+        match part {
+            1 => Some(Answer::from(input.trim().lines().count())),
+            _ => Some(Answer::from(0)),
+        }
+    }
+
+    #[test]
+    fn minimize_keeps_what_fails() {
+        let input = "1\n2\n\n3\n45\n\n6\n";
+        assert_eq!(minimize(input, |s| s.contains('4')), "4\n");
+        assert_eq!(minimize(input, |s| s.contains("2\n\n3")), "2\n\n3\n");
+        assert_eq!(minimize(input, |_| false), input);
+    }
+
+    /// Counts lines too, but panics on part 1 if a line has a 7 in it, and hangs on part 2
+    /// if a line has a 3 in it.
+    fn fragile_count(input: &str, part: u8) -> Option<Answer> {
+        match part {
+            1 if input.contains('7') => panic!("a seven"),
+            2 if input.contains('3') => {
+                thread::sleep(Duration::from_millis(500));
+                Some(Answer::from(0))
+            }
+            _ => count_lines(input, part),
+        }
+    }
+
+    #[test]
+    fn finds_and_minimizes_disagreement() {
+        let generate = |seed| inputgen::generate(9, seed).unwrap();
+        let disagreement = check(
+            97,
+            &Miscount,
+            count_lines,
+            generate,
+            0..10,
+            &[1, 2],
+            STEP_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(disagreement.part, 1);
+        assert_eq!(disagreement.input, "7\n");
+        assert_eq!(disagreement.expected, Ok(Answer::Int(1)));
+        assert_eq!(disagreement.actual, Ok(Answer::Int(2)));
+    }
+
+    #[test]
+    fn reports_failing_reference() {
+        let _lock = time_limits();
+        let generate = |seed| inputgen::generate(9, seed).unwrap();
+        let disagreement = check(
+            97,
+            &Miscount,
+            fragile_count,
+            generate,
+            0..10,
+            &[1],
+            STEP_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(disagreement.input, "7\n");
+        assert_eq!(
+            disagreement.expected,
+            Err(Failure::Panicked("a seven".to_string()))
+        );
+
+        let limit = Duration::from_millis(50);
+        let disagreement =
+            check(97, &Miscount, fragile_count, generate, 0..10, &[2], limit).unwrap();
+        assert_eq!(disagreement.input, "3\n");
+        assert_eq!(disagreement.expected, Err(Failure::TimedOut(limit)));
+    }
+
+    #[test]
+    fn solutions_agree_with_references() {
+        for puzzle in DAYS.iter() {
+            match check_day(puzzle.day(), 0..3, None) {
+                Ok(None) | Err(AocError::NoReference(_) | AocError::NoGenerator(_)) => (),
+                Ok(Some(disagreement)) => panic!("{}", disagreement),
+                Err(e) => panic!("{}", e),
+            }
+        }
+        assert!(matches!(
+            check_day(25, 0..1, None),
+            Err(AocError::UnknownDay(25))
+        ));
+    }
+}
@@ -1,10 +1,70 @@
+use crate::error::AocError;
 use std::fs;
+use std::io::{self, Read};
 
-pub fn load(data_dir: &str, day: u32, suffix: Option<&str>) -> String {
-    load_raw(data_dir, day, suffix).trim().replace('\r', "")
+/// Path that reads the puzzle input from standard input.
+pub const STDIN: &str = "-";
+
+pub fn load_raw(data_dir: &str, day: u32, suffix: Option<&str>) -> Result<String, AocError> {
+    let file = format!("{}/{}.txt", data_dir, input_name(day, suffix));
+    fs::read_to_string(&file).map_err(|e| AocError::ReadingInput(file, e))
+}
+
+/// Name of an input file without the extension, e.g. `10_ex1`.
+pub fn input_name(day: u32, suffix: Option<&str>) -> String {
+    format!("{:02}{}", day, suffix.unwrap_or(""))
+}
+
+/// Suffixes of all the inputs for a day in the data directory: the main input `NN.txt`
+/// (empty suffix) first if it exists, then variants such as `NN_ex1.txt` in name order.
+pub fn input_suffixes(data_dir: &str, day: u32) -> Result<Vec<String>, AocError> {
+    let prefix = input_name(day, None);
+    let mut suffixes = fs::read_dir(data_dir)
+        .map_err(|e| AocError::ReadingInput(data_dir.to_string(), e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let suffix = file_name.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+            match suffix.is_empty() | suffix.starts_with('_') {
+                true => Some(suffix.to_string()),
+                false => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    suffixes.sort();
+    Ok(suffixes)
 }
 
-pub fn load_raw(data_dir: &str, day: u32, suffix: Option<&str>) -> String {
-    let file = format!("{}/{:02}{}.txt", data_dir, day, suffix.unwrap_or(""));
-    fs::read_to_string(&file).unwrap_or_else(|_| panic!("Error reading file {}", file))
+/// Read puzzle input from a file, or from standard input if the path is [`STDIN`].
+pub fn read_input(path: &str) -> Result<String, AocError> {
+    if path == STDIN {
+        let mut data = String::new();
+        io::stdin()
+            .read_to_string(&mut data)
+            .map_err(|e| AocError::ReadingInput(String::from("<stdin>"), e))?;
+        Ok(data)
+    } else {
+        fs::read_to_string(path).map_err(|e| AocError::ReadingInput(path.to_string(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{input_name, input_suffixes};
+
+    #[test]
+    fn input_names() {
+        assert_eq!(input_name(1, None), "01");
+        assert_eq!(input_name(10, Some("_ex1")), "10_ex1");
+    }
+
+    #[test]
+    fn variants_in_data_dir() {
+        assert_eq!(
+            input_suffixes("puzzle-input", 10).unwrap(),
+            vec!["", "_ex1"]
+        );
+        assert_eq!(input_suffixes("puzzle-input", 1).unwrap(), vec![""]);
+        assert!(input_suffixes("puzzle-input", 25).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,74 @@
+//! Problems found at a place in the input, shown with the input line and a caret under the
+//! part that is wrong.
+
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Line number in the input, starting at 1.
+    pub line: usize,
+    /// Column in the line, counted in characters and starting at 1.
+    pub column: usize,
+    /// Number of characters to underline, at least 1.
+    pub width: usize,
+    /// The input line the diagnostic points into.
+    pub source_line: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// The first line of the rendered diagnostic, without the snippet.
+    pub fn summary(&self) -> String {
+        format!(
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Renders like
+///
+/// ```text
+/// line 2, column 5: could not parse `x` as u32
+///  2 | 2-4,x-8
+///    |     ^
+/// ```
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Tabs would shift the caret, so show them as single spaces.
+        let source = self.source_line.replace('\t', " ");
+        writeln!(f, "{}", self.summary())?;
+        writeln!(f, " {} | {}", number, source)?;
+        write!(
+            f,
+            " {} | {}{}",
+            gutter,
+            " ".repeat(self.column.saturating_sub(1)),
+            "^".repeat(self.width.max(1))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostic::Diagnostic;
+
+    #[test]
+    fn caret_under_the_problem() {
+        let diagnostic = Diagnostic {
+            line: 12,
+            column: 6,
+            width: 3,
+            source_line: "move\t2 from x to 1".to_string(),
+            message: "expected a number".to_string(),
+        };
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 12, column 6: expected a number\n 12 | move 2 from x to 1\n    |      ^^^"
+        );
+        assert_eq!(diagnostic.summary(), "line 12, column 6: expected a number");
+    }
+}
@@ -0,0 +1,55 @@
+use crate::answers::AnswersError;
+use crate::bench::BenchError;
+use crate::readme::ReadmeError;
+use crate::scaffold::ScaffoldError;
+use crate::solutions::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+};
+use thiserror::Error;
+
+/// Any error that can occur while loading inputs and running puzzles.
+#[derive(Error, Debug)]
+pub enum AocError {
+    #[error("Could not read input file {}: {}", .0, .1)]
+    ReadingInput(String, std::io::Error),
+    #[error("Puzzle for day {} not completed yet.", .0)]
+    UnknownDay(u32),
+    #[error("No input generator for day {}.", .0)]
+    NoGenerator(u32),
+    #[error("No reference solution for day {}.", .0)]
+    NoReference(u32),
+    #[error("No input linter for day {}.", .0)]
+    NoLinter(u32),
+    #[error(transparent)]
+    Answers(#[from] AnswersError),
+    #[error(transparent)]
+    Bench(#[from] BenchError),
+    #[error(transparent)]
+    Scaffold(#[from] ScaffoldError),
+    #[error(transparent)]
+    Readme(#[from] ReadmeError),
+    #[error(transparent)]
+    Day01(#[from] day01::PuzzleError),
+    #[error(transparent)]
+    Day02(#[from] day02::Day2Error),
+    #[error(transparent)]
+    Day03(#[from] day03::Day3Error),
+    #[error(transparent)]
+    Day04(#[from] day04::PuzzleError),
+    #[error(transparent)]
+    Day05(#[from] day05::PuzzleError),
+    #[error(transparent)]
+    Day06(#[from] day06::PuzzleError),
+    #[error(transparent)]
+    Day07(#[from] day07::PuzzleError),
+    #[error(transparent)]
+    Day08(#[from] day08::PuzzleError),
+    #[error(transparent)]
+    Day09(#[from] day09::PuzzleError),
+    #[error(transparent)]
+    Day10(#[from] day10::PuzzleError),
+    #[error(transparent)]
+    Day11(#[from] day11::PuzzleError),
+    #[error(transparent)]
+    Day12(#[from] day12::PuzzleError),
+}
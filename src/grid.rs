@@ -0,0 +1,311 @@
+//! Dense two-dimensional grid stored row by row, shared by the days whose input or output is
+//! a map of characters.
+
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+use thiserror::Error;
+
+/// Offsets to the four neighbours sharing an edge with a cell: up, down, left and right.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Offsets to all eight neighbours of a cell, including the diagonal ones.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    #[error("Grid has no cells.")]
+    Empty,
+    #[error("Row {} has {} cells, expected {}.", .row, .found, .expected)]
+    RaggedRow {
+        row: usize,
+        found: usize,
+        expected: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Position { row, col }
+    }
+
+    /// The position one step of `(rows, cols)` away, if neither coordinate goes below zero.
+    pub fn offset(self, (rows, cols): (isize, isize)) -> Option<Position> {
+        Some(Position {
+            row: self.row.checked_add_signed(rows)?,
+            col: self.col.checked_add_signed(cols)?,
+        })
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Build a grid from its rows, which must all have the same, non-zero, length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, values) in rows.into_iter().enumerate() {
+            if values.len() != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    found: values.len(),
+                    expected: width,
+                });
+            }
+            cells.extend(values);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        (pos.row < self.height) & (pos.col < self.width)
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        match self.contains(pos) {
+            true => self.cells.get(pos.row * self.width + pos.col),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => self.cells.get_mut(pos.row * self.width + pos.col),
+            false => None,
+        }
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Position::new(row, col)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The cells of one row, from left to right.
+    pub fn row(&self, row: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let start = (row.min(self.height)) * self.width;
+        let end = (start + self.width).min(self.cells.len());
+        self.cells[start..end].iter()
+    }
+
+    /// The cells of one column, from top to bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        let rows = if col < self.width { self.height } else { 0 };
+        (0..rows).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    /// The cells met when walking from `from` in steps of `step` until leaving the grid, not
+    /// counting the cell at `from` itself.
+    pub fn ray(
+        &self,
+        from: Position,
+        step: (isize, isize),
+    ) -> impl Iterator<Item = (Position, &T)> {
+        iter::successors(Some(from), move |pos| pos.offset(step))
+            .skip(1)
+            .map_while(|pos| self.get(pos).map(|value| (pos, value)))
+    }
+
+    /// The neighbours at the given offsets that are inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (Position, &'a T)> {
+        offsets
+            .iter()
+            .filter_map(move |step| pos.offset(*step))
+            .filter_map(|pos| self.get(pos).map(|value| (pos, value)))
+    }
+
+    /// The up to four neighbours sharing an edge with `pos`.
+    pub fn neighbours_4(&self, pos: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight neighbours surrounding `pos`, including the diagonal ones.
+    pub fn neighbours_8(&self, pos: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(pos, &SURROUNDING)
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, mut f: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, value)| f(pos, value)).collect(),
+        }
+    }
+
+    /// Draw the grid with one character per cell and a line per row, without a trailing
+    /// newline.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        (0..self.height)
+            .map(|row| self.row(row).map(&mut f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        match self.get(pos) {
+            Some(value) => value,
+            None => panic!(
+                "position {} outside of {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(value) => value,
+            None => panic!("position {} outside of {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, GridError, Position};
+
+    fn example() -> Grid<char> {
+        Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap()
+    }
+
+    fn chars<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn build_and_render() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Position::new(1, 2)], 'f');
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+        assert_eq!(grid.get(Position::new(0, 3)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(grid.render(|c| c.to_ascii_uppercase()), "ABC\nDEF");
+    }
+
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            Grid::from_rows(vec![vec!['a', 'b'], vec!['c']]),
+            Err(GridError::RaggedRow {
+                row: 1,
+                found: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(Grid::<char>::from_rows(vec![vec![]]), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn rows_columns_and_rays() {
+        let grid = example();
+        assert_eq!(chars(grid.row(1)), "def");
+        assert_eq!(chars(grid.row(1).rev()), "fed");
+        assert_eq!(chars(grid.column(2)), "cf");
+        assert_eq!(chars(grid.row(2)), "");
+        assert_eq!(chars(grid.column(3)), "");
+
+        let ray = |step| chars(grid.ray(Position::new(0, 0), step).map(|(_, c)| c));
+        assert_eq!(ray((0, 1)), "bc");
+        assert_eq!(ray((1, 1)), "e");
+        assert_eq!(ray((-1, 0)), "");
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = example();
+        let corner = Position::new(0, 0);
+        assert_eq!(chars(grid.neighbours_4(corner).map(|(_, c)| c)), "db");
+        assert_eq!(chars(grid.neighbours_8(corner).map(|(_, c)| c)), "bde");
+        let middle = Position::new(1, 1);
+        assert_eq!(chars(grid.neighbours_4(middle).map(|(_, c)| c)), "bdf");
+        assert_eq!(chars(grid.neighbours_8(middle).map(|(_, c)| c)), "abcdf");
+    }
+
+    #[test]
+    fn update_and_map() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[Position::new(1, 0)] = '#';
+        assert_eq!(grid.to_string(), "..\n#.");
+        let lit = grid.map(|_, c| *c == '#');
+        assert_eq!(lit.iter().filter(|(_, on)| **on).count(), 1);
+        assert_eq!(lit.positions().last(), Some(Position::new(1, 1)));
+    }
+}
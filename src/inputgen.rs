@@ -0,0 +1,559 @@
+//! Random puzzle inputs for stress and differential testing. Every generator makes an input
+//! in the format of the real puzzle input that the day's solution accepts, and the same seed
+//! always gives the same input.
+
+use std::collections::HashSet;
+
+/// Small seeded pseudo-random number generator (SplitMix64), so generated inputs are
+/// reproducible from their seed on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: usize, hi: usize) -> usize {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as usize
+    }
+
+    /// True with probability `1 / n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.between(1, n) == 1
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.between(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.between(0, i));
+        }
+    }
+}
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn letters(alphabet: &str) -> Vec<char> {
+    alphabet.chars().collect()
+}
+
+/// Day 1: blocks of calories, one block per elf, separated by blank lines.
+fn calories(rng: &mut Rng) -> String {
+    (0..rng.between(3, 50))
+        .map(|_| {
+            (0..rng.between(1, 8))
+                .map(|_| rng.between(1, 60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Day 2: rounds of rock paper scissors, e.g. `A Y`.
+fn strategy_guide(rng: &mut Rng) -> String {
+    (0..rng.between(1, 200))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 3: rucksacks whose halves share exactly one item type, in groups of three that share
+/// exactly one badge. Other items of a group come from disjoint pools so nothing else is
+/// common to all three.
+fn rucksacks(rng: &mut Rng) -> String {
+    let mut lines = Vec::new();
+    for _ in 0..rng.between(1, 50) {
+        let mut items = letters(LOWERCASE);
+        items.extend(letters(UPPERCASE));
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        for pool in items[1..].chunks(17) {
+            let shared = match rng.one_in(3) {
+                true => badge,
+                false => pool[0],
+            };
+            let mut halves = [pool[1..9].to_vec(), pool[9..].to_vec()];
+            let size = rng.between(2, 12);
+            let badge_half = rng.between(0, 1);
+            let mut line = String::new();
+            for (i, half) in halves.iter_mut().enumerate() {
+                let mut contents = vec![shared];
+                if (shared != badge) & (i == badge_half) {
+                    contents.push(badge);
+                }
+                while contents.len() < size {
+                    contents.push(*rng.pick(half));
+                }
+                rng.shuffle(&mut contents);
+                line.extend(contents);
+            }
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+/// Day 4: pairs of section ranges, e.g. `2-4,6-8`.
+fn section_pairs(rng: &mut Rng) -> String {
+    let range = |rng: &mut Rng| {
+        let (a, b) = (rng.between(1, 99), rng.between(1, 99));
+        format!("{}-{}", a.min(b), a.max(b))
+    };
+    (0..rng.between(1, 300))
+        .map(|_| format!("{},{}", range(rng), range(rng)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 5: a drawing of up to nine crate stacks followed by crane moves. Every move leaves at
+/// least one crate on the stack it takes from, so no stack is ever empty.
+fn crate_stacks(rng: &mut Rng) -> String {
+    let crates = letters(UPPERCASE);
+    let mut stacks = (0..rng.between(2, 9))
+        .map(|_| {
+            (0..rng.between(1, 8))
+                .map(|_| *rng.pick(&crates))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    stacks[0].push(*rng.pick(&crates));
+
+    let top = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut lines = (0..top)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|s| match s.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => String::from("   "),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    lines.push(
+        (1..=stacks.len())
+            .map(|i| format!(" {} ", i))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(String::new());
+
+    for _ in 0..rng.between(1, 60) {
+        let sources = (0..stacks.len())
+            .filter(|i| stacks[*i].len() > 1)
+            .collect::<Vec<_>>();
+        let from = *rng.pick(&sources);
+        let to = (from + rng.between(1, stacks.len() - 1)) % stacks.len();
+        let n = rng.between(1, stacks[from].len() - 1);
+        let height = stacks[from].len();
+        let moved = stacks[from].split_off(height - n);
+        stacks[to].extend(moved);
+        lines.push(format!("move {} from {} to {}", n, from + 1, to + 1));
+    }
+    lines.join("\n")
+}
+
+/// Day 6: a data stream with a start-of-message marker (fourteen distinct characters) after
+/// a prefix drawn from too few letters to contain one.
+fn data_stream(rng: &mut Rng) -> String {
+    let mut alphabet = letters(LOWERCASE);
+    rng.shuffle(&mut alphabet);
+    let few = &alphabet[..rng.between(3, 13)];
+    let mut stream = (0..rng.between(0, 200))
+        .map(|_| *rng.pick(few))
+        .collect::<String>();
+    rng.shuffle(&mut alphabet);
+    stream.extend(&alphabet[..14]);
+    stream.extend((0..rng.between(0, 100)).map(|_| *rng.pick(&alphabet)));
+    stream
+}
+
+#[derive(Debug, Default)]
+struct Directory {
+    name: String,
+    files: Vec<(String, usize)>,
+    children: Vec<usize>,
+}
+
+/// A name of one to eight lowercase letters that is not in `taken`.
+fn fresh_name(rng: &mut Rng, taken: &mut HashSet<String>, extension: bool) -> String {
+    let alphabet = letters(LOWERCASE);
+    loop {
+        let mut name = (0..rng.between(1, 8))
+            .map(|_| *rng.pick(&alphabet))
+            .collect::<String>();
+        if extension & rng.one_in(2) {
+            name = format!("{}.{}", name, rng.pick(&["txt", "dat", "log", "bin"]));
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Day 7: a shell transcript that lists every directory of a random file system once. The
+/// disk is 40 to 70 million units full, so there is always a directory to delete.
+fn terminal_output(rng: &mut Rng) -> String {
+    let mut dirs = vec![Directory {
+        name: String::from("/"),
+        ..Default::default()
+    }];
+    let mut unfilled = vec![(0, 0)];
+    while let Some((dir, depth)) = unfilled.pop() {
+        let mut taken = HashSet::new();
+        let n_dirs = match depth < 4 {
+            true => rng.between(usize::from(depth == 0), 4 - depth),
+            false => 0,
+        };
+        for _ in 0..n_dirs {
+            let name = fresh_name(rng, &mut taken, false);
+            dirs.push(Directory {
+                name,
+                ..Default::default()
+            });
+            let child = dirs.len() - 1;
+            dirs[dir].children.push(child);
+            unfilled.push((child, depth + 1));
+        }
+        for _ in 0..rng.between(0, 5) {
+            let name = fresh_name(rng, &mut taken, true);
+            dirs[dir].files.push((name, rng.between(1, 300_000)));
+        }
+    }
+
+    let target = rng.between(40_000_000, 70_000_000);
+    let mut used = dirs
+        .iter()
+        .flat_map(|d| d.files.iter().map(|(_, size)| size))
+        .sum::<usize>();
+    let mut file = 0;
+    while used < target {
+        let size = rng.between(1_000_000, 15_000_000).min(target - used);
+        let dir = rng.between(0, dirs.len() - 1);
+        dirs[dir].files.push((format!("big{}.bin", file), size));
+        used += size;
+        file += 1;
+    }
+
+    let mut lines = vec![String::from("$ cd /")];
+    list_directory(&dirs, 0, &mut lines, rng);
+    lines.join("\n")
+}
+
+fn list_directory(dirs: &[Directory], dir: usize, lines: &mut Vec<String>, rng: &mut Rng) {
+    lines.push(String::from("$ ls"));
+    let mut entries = dirs[dir]
+        .children
+        .iter()
+        .map(|c| format!("dir {}", dirs[*c].name))
+        .chain(
+            dirs[dir]
+                .files
+                .iter()
+                .map(|(name, size)| format!("{} {}", size, name)),
+        )
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut entries);
+    lines.extend(entries);
+    for child in dirs[dir].children.iter() {
+        lines.push(format!("$ cd {}", dirs[*child].name));
+        list_directory(dirs, *child, lines, rng);
+        lines.push(String::from("$ cd .."));
+    }
+}
+
+/// Day 8: a rectangular grid of tree heights.
+fn tree_grid(rng: &mut Rng) -> String {
+    let cols = rng.between(2, 20);
+    (0..rng.between(2, 20))
+        .map(|_| {
+            (0..cols)
+                .map(|_| char::from(b'0' + rng.between(0, 9) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 9: rope motions, e.g. `R 4`.
+fn rope_motions(rng: &mut Rng) -> String {
+    (0..rng.between(1, 300))
+        .map(|_| format!("{} {}", rng.pick(&['U', 'D', 'L', 'R']), rng.between(1, 20)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 10: a CPU program that runs for exactly the 240 cycles the CRT draws.
+fn cpu_program(rng: &mut Rng) -> String {
+    let mut lines = Vec::new();
+    let mut cycles = 0;
+    while cycles < 240 {
+        if (cycles == 239) | rng.one_in(3) {
+            lines.push(String::from("noop"));
+            cycles += 1;
+        } else {
+            lines.push(format!("addx {}", rng.between(0, 40) as isize - 20));
+            cycles += 2;
+        }
+    }
+    lines.join("\n")
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operation {
+    Add(i128),
+    Multiply(i128),
+    Square,
+}
+
+impl Operation {
+    fn apply(&self, old: i128) -> i128 {
+        match self {
+            Operation::Add(x) => old + x,
+            Operation::Multiply(x) => old * x,
+            Operation::Square => old * old,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MonkeySpec {
+    items: Vec<i128>,
+    operation: Operation,
+    divisor: i128,
+    targets: (usize, usize),
+}
+
+/// Whether the worry levels of both parts stay within `isize`. Part 1 is simulated for its
+/// 20 rounds. In part 2 levels are reduced modulo the product of the divisors after each
+/// round, and within a round an item can only be passed on to monkeys later in the list, so
+/// applying every operation in turn to the largest possible level bounds them.
+fn worry_levels_fit(monkeys: &[MonkeySpec]) -> bool {
+    let limit = isize::MAX as i128;
+    let mut items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<_>>();
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[i]) {
+                let new = monkey.operation.apply(old);
+                if new > limit {
+                    return false;
+                }
+                let new = new / 3;
+                let target = match new % monkey.divisor {
+                    0 => monkey.targets.0,
+                    _ => monkey.targets.1,
+                };
+                items[target].push(new);
+            }
+        }
+    }
+
+    let modulus = monkeys.iter().map(|m| m.divisor).product::<i128>();
+    let largest_item = monkeys.iter().flat_map(|m| m.items.iter()).max();
+    let mut bound = modulus.max(*largest_item.unwrap_or(&0));
+    for monkey in monkeys.iter() {
+        bound = monkey.operation.apply(bound);
+        if bound > limit {
+            return false;
+        }
+    }
+    true
+}
+
+/// Day 11: notes on monkeys with distinct prime divisors, each throwing to two other
+/// monkeys. Notes whose worry levels would overflow are drawn again.
+fn monkey_notes(rng: &mut Rng) -> String {
+    let monkeys = loop {
+        let n = rng.between(2, 8);
+        let mut primes = vec![2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut primes);
+        let squarer = rng.between(0, 2 * n);
+        let monkeys = (0..n)
+            .map(|id| {
+                let others = (0..n).filter(|m| *m != id).collect::<Vec<_>>();
+                let on_true = *rng.pick(&others);
+                let on_false = match others.len() {
+                    1 => on_true,
+                    _ => loop {
+                        let m = *rng.pick(&others);
+                        if m != on_true {
+                            break m;
+                        }
+                    },
+                };
+                let operation = match (id == squarer, rng.one_in(2)) {
+                    (true, _) => Operation::Square,
+                    (false, true) => Operation::Add(rng.between(1, 8) as i128),
+                    (false, false) => Operation::Multiply(rng.between(2, 19) as i128),
+                };
+                MonkeySpec {
+                    items: (0..rng.between(1, 6))
+                        .map(|_| rng.between(50, 99) as i128)
+                        .collect(),
+                    operation,
+                    divisor: primes[id],
+                    targets: (on_true, on_false),
+                }
+            })
+            .collect::<Vec<_>>();
+        if worry_levels_fit(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(id, m)| {
+            let items = m
+                .items
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let operation = match m.operation {
+                Operation::Add(x) => format!("old + {}", x),
+                Operation::Multiply(x) => format!("old * {}", x),
+                Operation::Square => String::from("old * old"),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                id, items, operation, m.divisor, m.targets.0, m.targets.1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Day 12: a height map with one start and one end. A path of steps right and down climbs
+/// from `S` to `E` one level at a time, so the end can always be reached.
+fn height_map(rng: &mut Rng) -> String {
+    let rows = rng.between(3, 20);
+    let cols = rng.between(28 - rows, 40);
+    let alphabet = letters(LOWERCASE);
+    let mut grid = (0..rows)
+        .map(|_| (0..cols).map(|_| *rng.pick(&alphabet)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut steps = vec![(1, 0); rows - 1];
+    steps.extend(vec![(0, 1); cols - 1]);
+    rng.shuffle(&mut steps);
+    let length = steps.len();
+    let (mut r, mut c) = (0, 0);
+    grid[r][c] = 'S';
+    for (i, (dr, dc)) in steps.iter().enumerate() {
+        r += dr;
+        c += dc;
+        grid[r][c] = alphabet[25 * (i + 1) / length];
+    }
+    grid[r][c] = 'E';
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+type Generator = fn(&mut Rng) -> String;
+
+/// Generators for the days whose input format is known, by day.
+static GENERATORS: &[(u32, Generator)] = &[
+    (1, calories),
+    (2, strategy_guide),
+    (3, rucksacks),
+    (4, section_pairs),
+    (5, crate_stacks),
+    (6, data_stream),
+    (7, terminal_output),
+    (8, tree_grid),
+    (9, rope_motions),
+    (10, cpu_program),
+    (11, monkey_notes),
+    (12, height_map),
+];
+
+/// Days that have an input generator.
+pub fn days() -> Vec<u32> {
+    GENERATORS.iter().map(|(day, _)| *day).collect()
+}
+
+/// A random input for the day made from the seed, or `None` if there is no generator for it.
+pub fn generate(day: u32, seed: u64) -> Option<String> {
+    let (_, generator) = GENERATORS.iter().find(|(d, _)| *d == day)?;
+    Some(generator(&mut Rng::new(seed)) + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inputgen::{days, generate, Rng};
+    use crate::solutions::{get, DAYS};
+    use crate::solve;
+
+    #[test]
+    fn rng_is_seeded() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.between(1, 6)).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+        assert!(draws(7).iter().all(|x| (1..=6).contains(x)));
+    }
+
+    #[test]
+    fn generators_are_for_registered_days() {
+        let registered = DAYS.iter().map(|p| p.day()).collect::<Vec<_>>();
+        assert!(days().iter().all(|day| registered.contains(day)));
+        assert_eq!(generate(25, 0), None);
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in days() {
+            assert_eq!(generate(day, 3), generate(day, 3));
+            assert_ne!(generate(day, 3), generate(day, 4));
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for day in days() {
+            for seed in 0..4 {
+                let input = generate(day, seed).unwrap();
+                let report = solve(get(day).unwrap(), &input);
+                assert!(
+                    report.is_ok(),
+                    "day {} seed {}: {:?}\n{}",
+                    day,
+                    seed,
+                    report.problem(),
+                    input
+                );
+            }
+        }
+    }
+}
@@ -1,35 +1,791 @@
-mod data;
-mod solutions;
-
-pub fn run_all(data_dir: &str) {
-    solutions::day01::main(data_dir);
-    solutions::day02::main(data_dir);
-    solutions::day03::main(data_dir);
-    solutions::day04::main(data_dir);
-    solutions::day05::main(data_dir);
-    solutions::day06::main(data_dir);
-    solutions::day07::main(data_dir);
-    solutions::day08::main(data_dir);
-    solutions::day09::main(data_dir);
-    solutions::day10::main(data_dir);
-    solutions::day11::main(data_dir);
-    solutions::day12::main(data_dir);
-}
-
-pub fn run_day(data_dir: &str, day: &usize) {
-    match day {
-        1 => solutions::day01::main(data_dir),
-        2 => solutions::day02::main(data_dir),
-        3 => solutions::day03::main(data_dir),
-        4 => solutions::day04::main(data_dir),
-        5 => solutions::day05::main(data_dir),
-        6 => solutions::day06::main(data_dir),
-        7 => solutions::day07::main(data_dir),
-        8 => solutions::day08::main(data_dir),
-        9 => solutions::day09::main(data_dir),
-        10 => solutions::day10::main(data_dir),
-        11 => solutions::day11::main(data_dir),
-        12 => solutions::day12::main(data_dir),
-        _ => panic!("Puzzle for day {} not completed yet.", day),
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod check;
+pub mod data;
+pub mod diagnostic;
+pub mod error;
+pub mod grid;
+pub mod inputgen;
+pub mod lint;
+pub mod output;
+pub mod parsing;
+pub mod pool;
+pub mod puzzle;
+pub mod readme;
+pub mod reference;
+pub mod report;
+pub mod scaffold;
+pub mod select;
+pub mod solutions;
+pub mod watch;
+
+use cache::Cache;
+use data::{input_name, input_suffixes, load_raw, read_input, STDIN};
+use error::AocError;
+use puzzle::{ParsedInput, Puzzle};
+use report::{DayReport, Failure, PartReport};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Run one step of a puzzle, turning both errors and panics into a [`Failure`].
+fn isolate<T>(step: impl FnOnce() -> Result<T, AocError>) -> Result<T, Failure> {
+    match panic::catch_unwind(AssertUnwindSafe(step)) {
+        Ok(Ok(x)) => Ok(x),
+        Ok(Err(e)) => Err(Failure::Error(e.to_string())),
+        Err(payload) => Err(Failure::Panicked(panic_message(payload.as_ref()))),
+    }
+}
+
+/// Number of steps that timed out but whose threads are still running.
+static OVERRUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// Steps that timed out and are still running in the background. Their threads cannot be
+/// stopped, so they keep using a CPU until they finish.
+pub fn overrunning_steps() -> usize {
+    OVERRUNNING.load(Ordering::SeqCst)
+}
+
+const RUNNING: u8 = 0;
+const FINISHED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+/// Run a step on its own thread and stop waiting for it after `limit`. A step that
+/// overruns is reported as timed out; its thread cannot be stopped and is left to finish in
+/// the background, counted by [`overrunning_steps`] until it does.
+fn isolate_with_limit<T: Send + 'static>(
+    limit: Option<Duration>,
+    step: impl FnOnce() -> Result<T, AocError> + Send + 'static,
+) -> Result<T, Failure> {
+    let Some(limit) = limit else {
+        return isolate(step);
+    };
+    let (sender, receiver) = mpsc::channel();
+    let state = Arc::new(AtomicU8::new(RUNNING));
+    let thread_state = Arc::clone(&state);
+    thread::spawn(move || {
+        let result = isolate(step);
+        let finished =
+            thread_state.compare_exchange(RUNNING, FINISHED, Ordering::SeqCst, Ordering::SeqCst);
+        match finished {
+            Ok(_) => drop(sender.send(result)),
+            Err(_) => drop(OVERRUNNING.fetch_sub(1, Ordering::SeqCst)),
+        }
+    });
+    match receiver.recv_timeout(limit) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            OVERRUNNING.fetch_add(1, Ordering::SeqCst);
+            let abandoned =
+                state.compare_exchange(RUNNING, ABANDONED, Ordering::SeqCst, Ordering::SeqCst);
+            match abandoned {
+                Ok(_) => Err(Failure::TimedOut(limit)),
+                // The step finished just after the limit, so its result is on its way.
+                Err(_) => {
+                    OVERRUNNING.fetch_sub(1, Ordering::SeqCst);
+                    receiver.recv().unwrap_or(Err(Failure::TimedOut(limit)))
+                }
+            }
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(Failure::Panicked("step thread exited early".to_string()))
+        }
+    }
+}
+
+/// Settings of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Number of threads to run days and their parts on.
+    pub jobs: usize,
+    /// Time limit for a day: parsing its input and solving its parts, one after the other.
+    pub day_limit: Option<Duration>,
+    /// Time limit for each part.
+    pub part_limit: Option<Duration>,
+    /// Only run this part, or both parts if not set.
+    pub part: Option<u8>,
+    /// Cache file to reuse answers from and save new answers to.
+    pub cache: Option<PathBuf>,
+    /// Solve every part even if its answer is cached, and refresh the cache.
+    pub recompute: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            jobs: 1,
+            day_limit: None,
+            part_limit: None,
+            part: None,
+            cache: None,
+            recompute: false,
+        }
+    }
+}
+
+impl RunOptions {
+    fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Time limit for a part of a day that has already spent `spent` parsing its input and
+    /// solving its other parts.
+    fn limit_for_part(&self, spent: Duration) -> Option<Duration> {
+        let day_left = self.day_limit.map(|d| d.saturating_sub(spent));
+        match (day_left, self.part_limit) {
+            (Some(d), Some(p)) => Some(d.min(p)),
+            (d, p) => d.or(p),
+        }
+    }
+}
+
+const PARTS: [u8; 2] = [1, 2];
+
+fn solve_part(
+    puzzle: &'static dyn Puzzle,
+    part: u8,
+    input: &Arc<ParsedInput>,
+    limit: Option<Duration>,
+) -> PartReport {
+    let input = Arc::clone(input);
+    let start = Instant::now();
+    let answer = isolate_with_limit(limit, move || match part {
+        1 => puzzle.part_1(&input),
+        _ => puzzle.part_2(&input),
+    });
+    PartReport {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+        cached: false,
+        verdict: None,
+    }
+}
+
+fn parse(
+    puzzle: &'static dyn Puzzle,
+    input_data: &str,
+    limit: Option<Duration>,
+) -> (DayReport, Option<Arc<ParsedInput>>) {
+    let mut report = DayReport::new(puzzle.day(), puzzle.title());
+    let input_data = input_data.to_string();
+    let start = Instant::now();
+    let parsed = isolate_with_limit(limit, move || puzzle.parse(&input_data));
+    report.parse_time = start.elapsed();
+    match parsed {
+        Ok(input) => (report, Some(Arc::new(input))),
+        Err(e) => {
+            report.error = Some(e);
+            (report, None)
+        }
+    }
+}
+
+/// Parse the input and solve both parts of a puzzle. Errors and panics are recorded in the
+/// report, and a panic in one part does not stop the other part from running.
+pub fn solve(puzzle: &'static dyn Puzzle, input_data: &str) -> DayReport {
+    solve_with_limit(puzzle, input_data, None)
+}
+
+/// [`solve`], with a time limit for parsing and for each part.
+pub fn solve_with_limit(
+    puzzle: &'static dyn Puzzle,
+    input_data: &str,
+    limit: Option<Duration>,
+) -> DayReport {
+    let (mut report, parsed) = parse(puzzle, input_data, limit);
+    if let Some(input) = parsed {
+        report.parts = PARTS
+            .iter()
+            .map(|part| solve_part(puzzle, *part, &input, limit))
+            .collect();
+    }
+    report
+}
+
+/// A puzzle with the input to run it on.
+struct Job {
+    puzzle: &'static dyn Puzzle,
+    /// Name of the input for the report.
+    input: String,
+    input_data: Result<String, Failure>,
+}
+
+impl Job {
+    fn new(
+        puzzle: &'static dyn Puzzle,
+        input: String,
+        input_data: Result<String, AocError>,
+    ) -> Self {
+        Job {
+            puzzle,
+            input,
+            input_data: input_data.map_err(|e| Failure::Error(e.to_string())),
+        }
+    }
+
+    fn from_data_dir(puzzle: &'static dyn Puzzle, data_dir: &str, suffix: Option<&str>) -> Self {
+        Job::new(
+            puzzle,
+            input_name(puzzle.day(), suffix),
+            load_raw(data_dir, puzzle.day(), suffix),
+        )
+    }
+}
+
+/// A job after its input was parsed, with the parts whose answers were found in the cache.
+struct ParsedJob {
+    report: DayReport,
+    input: Option<Arc<ParsedInput>>,
+    hash: Option<u64>,
+    cached: Vec<PartReport>,
+}
+
+fn load_cache(options: &RunOptions) -> Option<Cache> {
+    let path = options.cache.as_ref()?;
+    match Cache::load(path) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            log::warn!("{} Starting with an empty cache.", e);
+            Some(Cache::default())
+        }
+    }
+}
+
+fn cached_part(puzzle: &dyn Puzzle, part: u8, hash: u64, cache: &Cache) -> Option<PartReport> {
+    let answer = cache.get(puzzle.day(), part, puzzle.version(), hash)?;
+    Some(PartReport {
+        part,
+        answer: Ok(answer.clone()),
+        elapsed: Duration::ZERO,
+        cached: true,
+        verdict: None,
+    })
+}
+
+fn parse_job(job: &Job, options: &RunOptions, cache: Option<&Cache>) -> ParsedJob {
+    let data = match &job.input_data {
+        Ok(data) => data,
+        Err(e) => {
+            return ParsedJob {
+                report: DayReport {
+                    error: Some(e.clone()),
+                    ..DayReport::new(job.puzzle.day(), job.puzzle.title())
+                },
+                input: None,
+                hash: None,
+                cached: Vec::new(),
+            }
+        }
+    };
+    let hash = cache.map(|_| cache::input_hash(data));
+    let cached = match (cache, hash, options.recompute) {
+        (Some(cache), Some(hash), false) => PARTS
+            .iter()
+            .filter(|part| options.runs_part(**part))
+            .filter_map(|part| cached_part(job.puzzle, *part, hash, cache))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let n_parts = PARTS
+        .iter()
+        .filter(|part| options.runs_part(**part))
+        .count();
+    let (report, input) = match cached.len() == n_parts {
+        true => (DayReport::new(job.puzzle.day(), job.puzzle.title()), None),
+        false => parse(job.puzzle, data, options.day_limit),
+    };
+    ParsedJob {
+        report,
+        input,
+        hash,
+        cached,
+    }
+}
+
+/// Run the jobs on a pool of `options.jobs` threads. All inputs are parsed first, then the
+/// parts of every job share the pool. With a time limit for each day, the parts of a day run
+/// one after the other, so that each part only gets the time the day has left. Reports are
+/// in the order of the jobs. With a cache, cached answers are reused (an input is not even
+/// parsed if all its answers are cached) and new answers are saved.
+fn run_jobs(jobs: &[Job], options: &RunOptions) -> Vec<DayReport> {
+    let mut cache = load_cache(options);
+    let parsed = pool::parallel_map(options.jobs, jobs, |job| {
+        parse_job(job, options, cache.as_ref())
+    });
+
+    let tasks = parsed
+        .iter()
+        .enumerate()
+        .filter(|(_, job)| job.input.is_some())
+        .flat_map(|(i, job)| {
+            let parts = PARTS
+                .iter()
+                .filter(|part| job.cached.iter().all(|c| c.part != **part))
+                .filter(|part| options.runs_part(**part))
+                .copied()
+                .collect::<Vec<_>>();
+            match options.day_limit {
+                Some(_) => vec![(i, parts)],
+                None => parts.into_iter().map(|part| (i, vec![part])).collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut solved = pool::parallel_map(options.jobs, &tasks, |(i, parts)| {
+        let job = &parsed[*i];
+        let input = job.input.as_ref().expect("Only parsed inputs are solved.");
+        let mut spent = job.report.parse_time;
+        parts
+            .iter()
+            .map(|part| {
+                let limit = options.limit_for_part(spent);
+                let report = solve_part(jobs[*i].puzzle, *part, input, limit);
+                spent += report.elapsed;
+                (*i, report)
+            })
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+    .flatten()
+    .peekable();
+
+    let mut cache_changed = false;
+    let reports = parsed
+        .into_iter()
+        .zip(jobs.iter())
+        .enumerate()
+        .map(|(i, (parsed, job))| {
+            let mut parts = parsed.cached;
+            while let Some((_, part)) = solved.next_if(|(j, _)| *j == i) {
+                if let (Some(cache), Some(hash), Ok(answer)) =
+                    (&mut cache, parsed.hash, &part.answer)
+                {
+                    let puzzle = job.puzzle;
+                    cache.insert(
+                        puzzle.day(),
+                        part.part,
+                        puzzle.version(),
+                        hash,
+                        answer.clone(),
+                    );
+                    cache_changed = true;
+                }
+                parts.push(part);
+            }
+            parts.sort_by_key(|p| p.part);
+            DayReport {
+                input: job.input.clone(),
+                parts,
+                ..parsed.report
+            }
+        })
+        .collect();
+
+    if let (Some(cache), Some(path), true) = (&cache, &options.cache, cache_changed) {
+        if let Err(e) = cache.save(path) {
+            log::warn!("{}", e);
+        }
+    }
+    reports
+}
+
+fn run(puzzle: &'static dyn Puzzle, data_dir: &str, options: &RunOptions) -> DayReport {
+    let job = Job::from_data_dir(puzzle, data_dir, None);
+    run_jobs(&[job], options).remove(0)
+}
+
+/// Run every registered day. Each day is isolated from the others, so a day that fails or
+/// panics is reported and the remaining days still run.
+pub fn run_all(data_dir: &str, options: &RunOptions) -> Vec<DayReport> {
+    let jobs = solutions::DAYS
+        .iter()
+        .map(|puzzle| Job::from_data_dir(*puzzle, data_dir, None))
+        .collect::<Vec<_>>();
+    run_jobs(&jobs, options)
+}
+
+/// Run the given days, in day order. Fails before running anything if a day is not
+/// registered.
+pub fn run_days(
+    data_dir: &str,
+    days: &[u32],
+    options: &RunOptions,
+) -> Result<Vec<DayReport>, AocError> {
+    let jobs = days
+        .iter()
+        .map(|day| {
+            let puzzle = solutions::get(*day).ok_or(AocError::UnknownDay(*day))?;
+            Ok(Job::from_data_dir(puzzle, data_dir, None))
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+    Ok(run_jobs(&jobs, options))
+}
+
+pub fn run_day(data_dir: &str, day: &usize, options: &RunOptions) -> Result<DayReport, AocError> {
+    let puzzle = solutions::get(*day as u32).ok_or(AocError::UnknownDay(*day as u32))?;
+    Ok(run(puzzle, data_dir, options))
+}
+
+/// Run a day on the input read from a file, or from stdin if the path is `-`.
+pub fn run_day_on_input(
+    input_path: &str,
+    day: &usize,
+    options: &RunOptions,
+) -> Result<DayReport, AocError> {
+    let puzzle = solutions::get(*day as u32).ok_or(AocError::UnknownDay(*day as u32))?;
+    let input = match input_path {
+        STDIN => String::from("<stdin>"),
+        path => path.to_string(),
+    };
+    let job = Job::new(puzzle, input, read_input(input_path));
+    Ok(run_jobs(&[job], options).remove(0))
+}
+
+/// Run a day on every input for it in the data directory: the main input `NN.txt` and
+/// variants such as `NN_ex1.txt`.
+pub fn run_day_variants(
+    data_dir: &str,
+    day: &usize,
+    options: &RunOptions,
+) -> Result<Vec<DayReport>, AocError> {
+    let puzzle = solutions::get(*day as u32).ok_or(AocError::UnknownDay(*day as u32))?;
+    let jobs = input_suffixes(data_dir, puzzle.day())?
+        .iter()
+        .map(|suffix| Job::from_data_dir(puzzle, data_dir, Some(suffix)))
+        .collect::<Vec<_>>();
+    Ok(run_jobs(&jobs, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::Answer;
+    use crate::error::AocError;
+    use crate::puzzle::Solution;
+    use crate::report::{DayReport, Failure, Status};
+    use crate::solutions::{day02, day06, get};
+    use crate::{
+        isolate_with_limit, overrunning_steps, run, run_all, run_day, run_day_on_input,
+        run_day_variants, run_days, run_jobs, solve, Job, RunOptions,
+    };
+    use std::sync::{mpsc, Mutex, MutexGuard, PoisonError};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Held by the tests whose steps time out, so that only one of them changes the count of
+    /// overrunning steps at a time.
+    static TIME_LIMITS: Mutex<()> = Mutex::new(());
+
+    pub(crate) fn time_limits() -> MutexGuard<'static, ()> {
+        TIME_LIMITS.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wait until the number of overrunning steps is `count`, or give up after a while.
+    fn wait_for_overrunning(count: usize) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        while Instant::now() < deadline {
+            if overrunning_steps() == count {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    pub(crate) struct Panicky;
+
+    impl Solution for Panicky {
+        const DAY: u32 = 99;
+        const TITLE: &'static str = "Panicky";
+
+        type Input = usize;
+        type Output1 = usize;
+        type Output2 = usize;
+        type Error = day06::PuzzleError;
+
+        fn parse(input_data: &str) -> Result<usize, day06::PuzzleError> {
+            Ok(input_data.len())
+        }
+
+        fn part_1(_: &usize) -> Result<usize, day06::PuzzleError> {
+            panic!("part 1 exploded")
+        }
+
+        fn part_2(x: &usize) -> Result<usize, day06::PuzzleError> {
+            Ok(*x)
+        }
+    }
+
+    struct Slow;
+
+    impl Solution for Slow {
+        const DAY: u32 = 98;
+        const TITLE: &'static str = "Slow";
+
+        type Input = u64;
+        type Output1 = u64;
+        type Output2 = u64;
+        type Error = day06::PuzzleError;
+
+        fn parse(input_data: &str) -> Result<u64, day06::PuzzleError> {
+            Ok(input_data.trim().parse().unwrap_or_default())
+        }
+
+        fn part_1(millis: &u64) -> Result<u64, day06::PuzzleError> {
+            thread::sleep(Duration::from_millis(*millis));
+            Ok(*millis)
+        }
+
+        fn part_2(_: &u64) -> Result<u64, day06::PuzzleError> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn solve_reports_both_parts() {
+        let report = solve(get(1).unwrap(), "1000\n2000\n\n3000");
+        assert!(report.is_ok());
+        assert_eq!(report.day, 1);
+        assert_eq!(report.part(1).unwrap().answer, Ok(Answer::Int(3000)));
+        assert_eq!(report.part(2).unwrap().answer, Ok(Answer::Int(6000)));
+    }
+
+    #[test]
+    fn solve_reports_parse_error() {
+        let report = solve(&day02::Day02, "A Y\nB Q");
+        assert!(!report.is_ok());
+        assert!(report.error.is_some());
+        assert!(report.parts.is_empty());
+    }
+
+    #[test]
+    fn solve_isolates_panics() {
+        let report = solve(&Panicky, "abc");
+        assert_eq!(report.status(), Status::Panicked);
+        assert_eq!(
+            report.part(1).unwrap().answer,
+            Err(Failure::Panicked("part 1 exploded".to_string()))
+        );
+        assert_eq!(report.part(2).unwrap().answer, Ok(Answer::Int(3)));
+    }
+
+    #[test]
+    fn run_reports_missing_input() {
+        let report = run(get(1).unwrap(), "no-such-dir", &RunOptions::default());
+        assert!(!report.is_ok());
+        assert!(report
+            .error
+            .unwrap()
+            .to_string()
+            .contains("no-such-dir/01.txt"));
+    }
+
+    #[test]
+    fn run_on_input_file() {
+        let report =
+            run_day_on_input("puzzle-input/10_ex1.txt", &10, &RunOptions::default()).unwrap();
+        assert_eq!(report.part(1).unwrap().answer, Ok(Answer::Int(13140)));
+    }
+
+    #[test]
+    fn run_all_variants() {
+        let reports = run_day_variants(
+            "puzzle-input",
+            &10,
+            &RunOptions {
+                jobs: 4,
+                ..RunOptions::default()
+            },
+        )
+        .unwrap();
+        let inputs = reports.iter().map(|r| r.input.as_str()).collect::<Vec<_>>();
+        assert_eq!(inputs, vec!["10", "10_ex1"]);
+        assert_eq!(reports[1].part(1).unwrap().answer, Ok(Answer::Int(13140)));
+    }
+
+    #[test]
+    fn run_in_parallel() {
+        let dir = "no-such-dir";
+        let sequential = run_all(dir, &RunOptions::default());
+        let parallel = run_all(
+            dir,
+            &RunOptions {
+                jobs: 4,
+                ..RunOptions::default()
+            },
+        );
+        let days = |reports: &[DayReport]| reports.iter().map(|r| r.day).collect::<Vec<_>>();
+        assert_eq!(days(&parallel), days(&sequential));
+        assert!(parallel.iter().all(|r| r.error.is_some()));
+    }
+
+    #[test]
+    fn parts_time_out() {
+        let _lock = time_limits();
+        let options = RunOptions {
+            part_limit: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+        let jobs = [
+            Job::new(&Slow, String::from("slow"), Ok(String::from("2000"))),
+            Job::new(&Slow, String::from("quick"), Ok(String::from("1"))),
+        ];
+        let reports = run_jobs(&jobs, &options);
+        assert_eq!(reports[0].status(), Status::TimedOut);
+        assert_eq!(
+            reports[0].part(1).unwrap().answer,
+            Err(Failure::TimedOut(Duration::from_millis(50)))
+        );
+        assert_eq!(reports[0].part(2).unwrap().answer, Ok(Answer::Int(0)));
+        assert!(reports[1].is_ok());
+    }
+
+    #[test]
+    fn day_limit_covers_both_parts() {
+        let _lock = time_limits();
+        let options = RunOptions {
+            day_limit: Some(Duration::from_secs(2)),
+            jobs: 2,
+            ..RunOptions::default()
+        };
+        let jobs = [Job::new(
+            &Slow,
+            String::from("slow"),
+            Ok(String::from("20")),
+        )];
+        let report = run_jobs(&jobs, &options).remove(0);
+        assert_eq!(report.part(1).unwrap().answer, Ok(Answer::Int(20)));
+        assert_eq!(report.part(2).unwrap().answer, Ok(Answer::Int(0)));
+
+        // Part 1 uses up the whole day, which leaves no time at all for part 2.
+        let jobs = [Job::new(
+            &Slow,
+            String::from("slow"),
+            Ok(String::from("3000")),
+        )];
+        let report = run_jobs(&jobs, &options).remove(0);
+        assert!(matches!(
+            report.part(1).unwrap().answer,
+            Err(Failure::TimedOut(_))
+        ));
+        assert_eq!(
+            report.part(2).unwrap().answer,
+            Err(Failure::TimedOut(Duration::ZERO))
+        );
+    }
+
+    #[test]
+    fn overrunning_steps_are_counted() {
+        let _lock = time_limits();
+        assert!(
+            wait_for_overrunning(0),
+            "steps of other tests still running"
+        );
+        let (release, released) = mpsc::channel::<()>();
+        let answer = isolate_with_limit(Some(Duration::from_millis(10)), move || {
+            released.recv().ok();
+            Ok(())
+        });
+        assert_eq!(answer, Err(Failure::TimedOut(Duration::from_millis(10))));
+        assert_eq!(overrunning_steps(), 1);
+        release.send(()).unwrap();
+        assert!(wait_for_overrunning(0));
+    }
+
+    #[test]
+    fn day_limit_includes_parsing() {
+        let options = RunOptions {
+            day_limit: Some(Duration::from_secs(3)),
+            part_limit: Some(Duration::from_secs(2)),
+            ..RunOptions::default()
+        };
+        assert_eq!(
+            options.limit_for_part(Duration::from_millis(500)),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            options.limit_for_part(Duration::from_millis(1500)),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            RunOptions::default().limit_for_part(Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn run_selected_days_and_part() {
+        let options = RunOptions {
+            part: Some(2),
+            ..RunOptions::default()
+        };
+        let reports = run_days("puzzle-input", &[1, 6], &options).unwrap();
+        assert_eq!(
+            reports.iter().map(|r| r.day).collect::<Vec<_>>(),
+            vec![1, 6]
+        );
+        assert!(reports
+            .iter()
+            .all(|r| r.parts.len() == 1 && r.part(2).is_some()));
+        assert!(matches!(
+            run_days("puzzle-input", &[1, 25], &options),
+            Err(AocError::UnknownDay(25))
+        ));
+    }
+
+    #[test]
+    fn run_with_cache() {
+        let path = std::env::temp_dir().join(format!("aoc-cache-{}.json", uuid::Uuid::new_v4()));
+        let options = RunOptions {
+            cache: Some(path.clone()),
+            ..RunOptions::default()
+        };
+        let job = || {
+            Job::new(
+                get(1).unwrap(),
+                String::from("01"),
+                Ok(String::from("1\n\n2")),
+            )
+        };
+        let first = run_jobs(&[job()], &options).remove(0);
+        let second = run_jobs(&[job()], &options).remove(0);
+        assert!(first.parts.iter().all(|p| !p.cached));
+        assert!(second.parts.iter().all(|p| p.cached));
+        assert_eq!(second.part(1).unwrap().answer, Ok(Answer::Int(2)));
+
+        let recomputed = run_jobs(
+            &[job()],
+            &RunOptions {
+                recompute: true,
+                ..options
+            },
+        )
+        .remove(0);
+        assert!(recomputed.parts.iter().all(|p| !p.cached));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn run_unknown_day() {
+        assert!(matches!(
+            run_day("puzzle-input", &25, &RunOptions::default()),
+            Err(AocError::UnknownDay(25))
+        ));
     }
 }
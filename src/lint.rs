@@ -0,0 +1,714 @@
+//! Checks of puzzle inputs that do not solve the puzzle but list every problem they find,
+//! e.g. for hand-edited or generated inputs. Each day has a linter that knows its format,
+//! including the rules the parsers take for granted, such as monkeys only throwing to
+//! monkeys that exist or crane moves only taking crates that are there.
+
+use crate::diagnostic::Diagnostic;
+use crate::error::AocError;
+use crate::parsing::{blocks, end, lines, Span};
+use crate::solutions::day09;
+use std::collections::{HashMap, HashSet};
+
+/// Lists the problems of an input for one day.
+pub type Linter = fn(&str) -> Vec<Diagnostic>;
+
+/// Keep the value of a check, or record its problem.
+fn ok<T>(problems: &mut Vec<Diagnostic>, result: Result<T, Diagnostic>) -> Option<T> {
+    result.map_err(|problem| problems.push(problem)).ok()
+}
+
+/// Sums of calories, for each elf and for the top three elves, must fit the `u32` the
+/// solution adds them up in.
+fn calories(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let mut elves = Vec::new();
+    for block in blocks(input) {
+        let mut total = Some(0u32);
+        for line in block.iter() {
+            if let Some(calories) = ok(&mut problems, line.parse::<u32>()) {
+                total = total.and_then(|t| t.checked_add(calories));
+            }
+        }
+        match total {
+            Some(total) => elves.push((total, block[0])),
+            None => problems.push(block[0].error("calories of the elf do not fit in 32 bits")),
+        }
+    }
+    elves.sort_by_key(|(total, _)| std::cmp::Reverse(*total));
+    let mut top_three = Some(0u32);
+    for (total, first_line) in elves.iter().take(3) {
+        top_three = top_three.and_then(|t| t.checked_add(*total));
+        if top_three.is_none() {
+            problems
+                .push(first_line.error("calories of the top three elves do not fit in 32 bits"));
+            break;
+        }
+    }
+    problems
+}
+
+fn strategy_guide(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    for line in lines(input) {
+        let Some([opponent, strategy]) = ok(&mut problems, line.fields("{} {}")) else {
+            continue;
+        };
+        if !["A", "B", "C"].contains(&opponent.text) {
+            problems.push(opponent.error("expected `A`, `B` or `C`"));
+        }
+        if !["X", "Y", "Z"].contains(&strategy.text) {
+            problems.push(strategy.error("expected `X`, `Y` or `Z`"));
+        }
+    }
+    problems
+}
+
+/// Every rucksack has exactly one item type in both compartments, and every group of three
+/// exactly one item type in common, its badge.
+fn rucksacks(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let lines = lines(input).iter().map(|l| l.trim()).collect::<Vec<_>>();
+    let common = |sets: &[HashSet<char>]| {
+        let mut common = sets[0].clone();
+        sets[1..]
+            .iter()
+            .for_each(|set| common.retain(|c| set.contains(c)));
+        let mut common = common.into_iter().collect::<Vec<_>>();
+        common.sort();
+        common
+    };
+    let listed = |items: &[char]| {
+        let items = items.iter().map(|c| format!("`{}`", c));
+        items.collect::<Vec<_>>().join(", ")
+    };
+    for line in lines.iter() {
+        let bad = line.chars().filter(|(_, c)| !c.is_ascii_alphabetic());
+        for (item, _) in bad {
+            problems.push(item.error("expected an item from `a` to `z` or `A` to `Z`"));
+        }
+        let items = line.text.chars().collect::<Vec<_>>();
+        if items.len() % 2 == 1 {
+            let message = "expected an even number of items, half in each compartment";
+            problems.push(line.error(message));
+            continue;
+        }
+        let (first, second) = items.split_at(items.len() / 2);
+        let shared = common(&[
+            first.iter().copied().collect(),
+            second.iter().copied().collect(),
+        ]);
+        if shared.len() != 1 {
+            let message = format!(
+                "expected one item type in both compartments, found {}",
+                shared.len()
+            );
+            problems.push(line.error(match shared.is_empty() {
+                true => message,
+                false => format!("{}: {}", message, listed(&shared)),
+            }));
+        }
+    }
+    if lines.len() % 3 != 0 {
+        let message = format!("expected groups of 3 rucksacks, found {}", lines.len());
+        problems.push(end(input).error(message));
+    }
+    for group in lines.chunks_exact(3) {
+        let sets = group
+            .iter()
+            .map(|line| line.text.chars().collect())
+            .collect::<Vec<_>>();
+        let badges = common(&sets);
+        if badges.len() != 1 {
+            let message = format!(
+                "expected one item type in all 3 rucksacks of the group, found {}",
+                badges.len()
+            );
+            problems.push(group[0].error(match badges.is_empty() {
+                true => message,
+                false => format!("{}: {}", message, listed(&badges)),
+            }));
+        }
+    }
+    problems
+}
+
+fn section_pairs(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    for line in lines(input) {
+        let Some(fields) = ok(&mut problems, line.fields::<4>("{}-{},{}-{}")) else {
+            continue;
+        };
+        for [from, to] in [[fields[0], fields[1]], [fields[2], fields[3]]] {
+            let (Some(start), Some(end)) = (
+                ok(&mut problems, from.parse::<u32>()),
+                ok(&mut problems, to.parse::<u32>()),
+            ) else {
+                continue;
+            };
+            if start > end {
+                let message = format!("section range {}-{} ends before it starts", start, end);
+                problems.push(from.error(message));
+            }
+        }
+    }
+    problems
+}
+
+/// The drawing has a crate `[A]` or a gap in each slot, no crate floats above a gap, and
+/// every move takes no more crates than its stack holds at that point.
+fn crate_stacks(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let lines = lines(input);
+    let Some(numbers) = lines.iter().position(|l| l.text.trim().starts_with('1')) else {
+        return vec![end(input).error("expected the line numbering the stacks")];
+    };
+
+    let mut n_stacks = 0;
+    for (i, number) in lines[numbers].words().enumerate() {
+        match number.parse::<usize>() {
+            Ok(n) if n == i + 1 => n_stacks = n,
+            _ => problems.push(number.error(format!("expected stack number {}", i + 1))),
+        }
+    }
+
+    // Crates of each row from the top, by stack.
+    let mut rows = Vec::new();
+    for line in lines[..numbers].iter() {
+        let mut row = vec![false; n_stacks];
+        for word in line.words() {
+            let offset = word.column - line.column;
+            let letter = word.text.chars().nth(1).filter(char::is_ascii_uppercase);
+            let stack = offset / 4;
+            if (offset % 4 != 0) | (word.text.chars().count() != 3) | letter.is_none() {
+                problems.push(word.error("expected a crate like `[A]` in line with the stacks"));
+            } else if !word.text.starts_with('[') | !word.text.ends_with(']') {
+                problems.push(word.error("expected a crate like `[A]`"));
+            } else if stack >= n_stacks {
+                let message = format!("crate on stack {}, but there are {}", stack + 1, n_stacks);
+                problems.push(word.error(message));
+            } else {
+                row[stack] = true;
+            }
+        }
+        rows.push((line, row));
+    }
+    let mut heights = vec![0; n_stacks];
+    for (i, (line, row)) in rows.iter().enumerate() {
+        for stack in 0..n_stacks {
+            let gap_below = rows.get(i + 1).is_some_and(|(_, below)| !below[stack]);
+            if row[stack] & gap_below {
+                let message = format!("crate on stack {} has a gap below it", stack + 1);
+                problems.push(line.error(message));
+            }
+            heights[stack] += row[stack] as usize;
+        }
+    }
+
+    for line in lines[numbers + 1..].iter() {
+        let Some([n, from, to]) = ok(&mut problems, line.fields("move {} from {} to {}")) else {
+            continue;
+        };
+        let mut stack = |x: Span| {
+            let i = ok(&mut problems, x.parse::<usize>())?;
+            if !(1..=n_stacks).contains(&i) {
+                let message = format!("there is no stack {}, only 1 to {}", i, n_stacks);
+                problems.push(x.error(message));
+                return None;
+            }
+            Some(i - 1)
+        };
+        let (from_stack, to_stack) = (stack(from), stack(to));
+        let (Some(count), Some(from_stack), Some(to_stack)) =
+            (ok(&mut problems, n.parse::<usize>()), from_stack, to_stack)
+        else {
+            continue;
+        };
+        if count > heights[from_stack] {
+            let message = format!(
+                "cannot move {} crates from stack {}, it has {}",
+                count,
+                from_stack + 1,
+                heights[from_stack]
+            );
+            problems.push(n.error(message));
+        }
+        let moved = count.min(heights[from_stack]);
+        heights[from_stack] -= moved;
+        heights[to_stack] += moved;
+    }
+    for (stack, height) in heights.iter().enumerate() {
+        if *height == 0 {
+            let message = format!("stack {} is empty after the last move", stack + 1);
+            problems.push(end(input).error(message));
+        }
+    }
+    problems
+}
+
+/// A single line of lowercase letters with a start-of-packet and a start-of-message marker.
+fn data_stream(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let lines = lines(input);
+    for line in lines.iter().skip(1) {
+        problems.push(line.error("expected the datastream on a single line"));
+    }
+    let Some(stream) = lines.first().map(|line| line.trim()) else {
+        return problems;
+    };
+    for (c, _) in stream.chars().filter(|(_, c)| !c.is_ascii_lowercase()) {
+        problems.push(c.error("expected a letter from `a` to `z`"));
+    }
+    let chars = stream.text.chars().collect::<Vec<_>>();
+    for size in [4, 14] {
+        let found = chars
+            .windows(size)
+            .any(|w| w.iter().collect::<HashSet<_>>().len() == size);
+        if !found {
+            let message = format!("no {} different characters in a row", size);
+            problems.push(stream.error(message));
+        }
+    }
+    problems
+}
+
+/// The transcript starts in `/`, only lists after `$ ls` and only enters directories listed
+/// in the current one.
+fn terminal_output(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let lines = lines(input);
+    let Some(first) = lines.first() else {
+        return problems;
+    };
+    ok(&mut problems, first.fields::<0>("$ cd /"));
+
+    let path = |cwd: &[&str]| format!("/{}", cwd.join("/"));
+    let mut cwd: Vec<&str> = Vec::new();
+    let mut listed: HashMap<Vec<&str>, HashSet<&str>> = HashMap::new();
+    let mut listing = false;
+    for line in lines.iter().skip(1).map(|l| l.trim()) {
+        if line.text.starts_with('$') {
+            listing = false;
+            match line.words().nth(1).map(|command| command.text) {
+                Some("cd") => {
+                    let Some([dir]) = ok(&mut problems, line.fields("$ cd {}")) else {
+                        continue;
+                    };
+                    match dir.text {
+                        "/" => cwd.clear(),
+                        ".." => {
+                            if cwd.pop().is_none() {
+                                problems.push(dir.error("already in the outermost directory"));
+                            }
+                        }
+                        name => {
+                            let message = match listed.get(&cwd) {
+                                Some(dirs) if dirs.contains(name) => None,
+                                Some(_) => Some("is not listed in"),
+                                None => Some("is unknown, nothing was listed yet in"),
+                            };
+                            if let Some(message) = message {
+                                let message = format!("`{}` {} {}", name, message, path(&cwd));
+                                problems.push(dir.error(message));
+                            }
+                            // Enter it anyway, so that one bad `cd` is reported only once.
+                            cwd.push(name);
+                        }
+                    }
+                }
+                Some("ls") => {
+                    if ok(&mut problems, line.fields::<0>("$ ls")).is_some() {
+                        listing = true;
+                        listed.entry(cwd.clone()).or_default();
+                    }
+                }
+                _ => problems.push(line.error("expected `$ cd` or `$ ls`")),
+            }
+        } else if !listing {
+            problems.push(line.error("expected a command, output must follow `$ ls`"));
+        } else if line.text.starts_with("dir") {
+            if let Some([name]) = ok(&mut problems, line.fields("dir {}")) {
+                listed.entry(cwd.clone()).or_default().insert(name.text);
+            }
+        } else if let Some([size, _]) = ok(&mut problems, line.fields("{} {}")) {
+            ok(&mut problems, size.parse::<usize>());
+        }
+    }
+    problems
+}
+
+/// Check the characters and the shape of a map, and return the cells that are accepted.
+fn char_map<'a>(
+    input: &'a str,
+    expected: &str,
+    accepts: impl Fn(char) -> bool,
+    problems: &mut Vec<Diagnostic>,
+) -> Vec<(Span<'a>, char)> {
+    let lines = lines(input).iter().map(|l| l.trim()).collect::<Vec<_>>();
+    let width = lines.first().map_or(0, |line| line.text.chars().count());
+    let mut cells = Vec::new();
+    for line in lines.iter() {
+        for (span, c) in line.chars() {
+            match accepts(c) {
+                true => cells.push((span, c)),
+                false => problems.push(span.error(format!("expected {}", expected))),
+            }
+        }
+        let length = line.text.chars().count();
+        if length != width {
+            let message = format!("row has {} cells, the first has {}", length, width);
+            problems.push(line.error(message));
+        }
+    }
+    cells
+}
+
+fn tree_grid(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    char_map(input, "a digit", |c| c.is_ascii_digit(), &mut problems);
+    problems
+}
+
+fn rope_motions(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let mut total_steps: usize = 0;
+    for line in lines(input) {
+        let Some([direction, steps]) = ok(&mut problems, line.fields("{} {}")) else {
+            continue;
+        };
+        if !["U", "D", "L", "R"].contains(&direction.text) {
+            problems.push(direction.error("expected `U`, `D`, `L` or `R`"));
+        }
+        if let Some(n) = ok(&mut problems, steps.parse::<usize>()) {
+            let before = total_steps;
+            total_steps = total_steps.saturating_add(n);
+            if before <= day09::MAX_STEPS && total_steps > day09::MAX_STEPS {
+                let message = format!("the motions take more than {} steps", day09::MAX_STEPS);
+                problems.push(steps.error(message));
+            }
+        }
+    }
+    problems
+}
+
+/// The instructions run for exactly the 240 cycles it takes to draw the screen.
+fn cpu_program(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let mut cycles = 0;
+    let mut register = Some(1isize);
+    for line in lines(input).iter().map(|l| l.trim()) {
+        match line.words().next().map(|op| op.text) {
+            Some("noop") => {
+                if ok(&mut problems, line.fields::<0>("noop")).is_some() {
+                    cycles += 1;
+                }
+            }
+            Some("addx") => {
+                let Some([x]) = ok(&mut problems, line.fields("addx {}")) else {
+                    continue;
+                };
+                if let Some(add) = ok(&mut problems, x.parse::<isize>()) {
+                    cycles += 2;
+                    if let Some(r) = register {
+                        register = r.checked_add(add);
+                        if register.is_none() {
+                            problems.push(x.error("register X overflows"));
+                        }
+                    }
+                }
+            }
+            _ => problems.push(line.error("expected `noop` or `addx`")),
+        }
+    }
+    if cycles != 240 {
+        let message = format!(
+            "the program runs for {} cycles, the screen takes 240",
+            cycles
+        );
+        problems.push(end(input).error(message));
+    }
+    problems
+}
+
+/// Every monkey is described once, tests divisibility by a positive number, and throws
+/// only to other monkeys that exist.
+fn monkey_notes(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let mut ids: HashMap<usize, Span> = HashMap::new();
+    let mut throws = Vec::new();
+    let blocks = blocks(input);
+    for block in blocks.iter() {
+        let [id, items, operation, test, if_true, if_false] = block[..] else {
+            let message = format!("expected 6 lines about a monkey, found {}", block.len());
+            problems.push(block[0].error(message));
+            continue;
+        };
+        let id = ok(&mut problems, id.fields("Monkey {}:"))
+            .and_then(|[id]| Some((id, ok(&mut problems, id.parse::<usize>())?)));
+        if let Some((span, id)) = id {
+            if let Some(first) = ids.insert(id, span) {
+                let message = format!("monkey {} is already described on line {}", id, first.line);
+                problems.push(span.error(message));
+            }
+        }
+
+        if let Some(items) = ok(&mut problems, items.after("Starting items:")) {
+            ok(&mut problems, items.ints::<isize>());
+        }
+
+        let expression = ok(&mut problems, operation.after("Operation: new ="));
+        if let Some([x, op, y]) = expression.and_then(|e| ok(&mut problems, e.fields("{} {} {}"))) {
+            for operand in [x, y].into_iter().filter(|o| o.text != "old") {
+                ok(&mut problems, operand.parse::<isize>());
+            }
+            match op.text {
+                "+" | "-" | "*" => (),
+                "/" => problems.push(op.error("division is not supported")),
+                _ => problems.push(op.error("expected `+`, `-` or `*`")),
+            }
+        }
+
+        let divisor = ok(&mut problems, test.fields("Test: divisible by {}"))
+            .and_then(|[d]| Some((d, ok(&mut problems, d.parse::<isize>())?)));
+        if let Some((span, divisor)) = divisor {
+            if divisor <= 0 {
+                problems.push(span.error("expected a positive divisor"));
+            }
+        }
+
+        for (line, template) in [
+            (if_true, "If true: throw to monkey {}"),
+            (if_false, "If false: throw to monkey {}"),
+        ] {
+            let target = ok(&mut problems, line.fields(template))
+                .and_then(|[t]| Some((t, ok(&mut problems, t.parse::<usize>())?)));
+            if let Some(target) = target {
+                throws.push((id.map(|(_, id)| id), target));
+            }
+        }
+    }
+
+    for (thrower, (span, target)) in throws {
+        if !ids.contains_key(&target) {
+            problems.push(span.error(format!("there is no monkey {}", target)));
+        } else if thrower == Some(target) {
+            problems.push(span.error(format!("monkey {} throws to itself", target)));
+        }
+    }
+    if blocks.len() < 2 {
+        let message = format!("expected at least 2 monkeys, found {}", blocks.len());
+        problems.push(end(input).error(message));
+    }
+    problems
+}
+
+/// Heights are letters, with exactly one start `S` and one end `E`.
+fn height_map(input: &str) -> Vec<Diagnostic> {
+    let mut problems = Vec::new();
+    let cells = char_map(
+        input,
+        "a height from `a` to `z`, `S` or `E`",
+        |c| c.is_ascii_lowercase() | (c == 'S') | (c == 'E'),
+        &mut problems,
+    );
+    for (marker, name) in [('S', "start"), ('E', "end")] {
+        let mut found = cells.iter().filter(|(_, c)| *c == marker).map(|(s, _)| s);
+        let Some(first) = found.next() else {
+            let message = format!("no {} `{}` in the map", name, marker);
+            problems.push(end(input).error(message));
+            continue;
+        };
+        for other in found {
+            let message = format!(
+                "another {} `{}`, the first is on line {}, column {}",
+                name, marker, first.line, first.column
+            );
+            problems.push(other.error(message));
+        }
+    }
+    problems
+}
+
+static LINTERS: &[(u32, Linter)] = &[
+    (1, calories),
+    (2, strategy_guide),
+    (3, rucksacks),
+    (4, section_pairs),
+    (5, crate_stacks),
+    (6, data_stream),
+    (7, terminal_output),
+    (8, tree_grid),
+    (9, rope_motions),
+    (10, cpu_program),
+    (11, monkey_notes),
+    (12, height_map),
+];
+
+/// Look up the linter for a day.
+pub fn get(day: u32) -> Option<Linter> {
+    LINTERS.iter().find(|(d, _)| *d == day).map(|(_, l)| *l)
+}
+
+/// Every problem of an input for a day, in the order they appear in it.
+pub fn lint(day: u32, input: &str) -> Result<Vec<Diagnostic>, AocError> {
+    let linter = get(day).ok_or(AocError::NoLinter(day))?;
+    if input.trim().is_empty() {
+        return Ok(vec![end(input).error("the input is empty")]);
+    }
+    let mut problems = linter(input);
+    problems.sort_by_key(|p| (p.line, p.column));
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::load_raw;
+    use crate::error::AocError;
+    use crate::inputgen;
+    use crate::lint::{get, lint, LINTERS};
+    use crate::solutions::DAYS;
+
+    /// Line, column and message of each problem.
+    fn problems(day: u32, input: &str) -> Vec<(usize, usize, String)> {
+        lint(day, input)
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.line, p.column, p.message))
+            .collect()
+    }
+
+    fn at(line: usize, column: usize, message: &str) -> (usize, usize, String) {
+        (line, column, message.to_string())
+    }
+
+    #[test]
+    fn linters_are_for_registered_days() {
+        let registered = DAYS.iter().map(|p| p.day()).collect::<Vec<_>>();
+        assert!(LINTERS.iter().all(|(day, _)| registered.contains(day)));
+        assert!(matches!(lint(25, ""), Err(AocError::NoLinter(25))));
+    }
+
+    #[test]
+    fn valid_inputs_have_no_problems() {
+        for puzzle in DAYS.iter().filter(|p| get(p.day()).is_some()) {
+            let day = puzzle.day();
+            if let Ok(input) = load_raw("puzzle-input", day, None) {
+                assert_eq!(problems(day, &input), vec![], "day {} puzzle input", day);
+            }
+            for (seed, input) in
+                (0..5).filter_map(|seed| Some((seed, inputgen::generate(day, seed)?)))
+            {
+                assert_eq!(problems(day, &input), vec![], "day {} seed {}", day, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn monkey_targets_must_exist() {
+        let input = "
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 1:
+  Starting items: 54
+  Operation: new = old / 6
+  Test: divisible by 0
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+        assert_eq!(
+            problems(11, input),
+            vec![
+                at(6, 30, "there is no monkey 2"),
+                at(7, 31, "monkey 0 throws to itself"),
+                at(11, 24, "division is not supported"),
+                at(12, 22, "expected a positive divisor"),
+            ]
+        );
+    }
+
+    #[test]
+    fn crane_moves_must_stay_in_bounds() {
+        let input = "
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 4
+move 3 from 1 to 3
+move 2 from 0 to 1
+move 4 from 2 to 1
+";
+        assert_eq!(
+            problems(5, input),
+            vec![
+                at(7, 18, "there is no stack 4, only 1 to 3"),
+                at(8, 6, "cannot move 3 crates from stack 1, it has 2"),
+                at(9, 13, "there is no stack 0, only 1 to 3"),
+                at(10, 6, "cannot move 4 crates from stack 2, it has 3"),
+                at(10, 19, "stack 2 is empty after the last move"),
+            ]
+        );
+        assert_eq!(
+            problems(5, "[A]\n    [B]\n 1   2\n"),
+            vec![at(1, 1, "crate on stack 1 has a gap below it")]
+        );
+    }
+
+    #[test]
+    fn one_start_and_one_end() {
+        assert_eq!(
+            problems(12, "SabS\nab?d\nabcd\n"),
+            vec![
+                at(1, 4, "another start `S`, the first is on line 1, column 1"),
+                at(2, 3, "expected a height from `a` to `z`, `S` or `E`"),
+                at(3, 5, "no end `E` in the map"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cd_only_into_listed_directories() {
+        let input = "
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+$ cd b
+$ cd ..
+$ cd ..
+$ cd a
+584 i
+";
+        assert_eq!(
+            problems(7, input),
+            vec![
+                at(6, 6, "`b` is not listed in /"),
+                at(8, 6, "already in the outermost directory"),
+                at(10, 1, "expected a command, output must follow `$ ls`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn sums_must_not_overflow() {
+        assert_eq!(
+            problems(1, "4294967295\n\n1\n"),
+            vec![at(
+                3,
+                1,
+                "calories of the top three elves do not fit in 32 bits"
+            )]
+        );
+        assert_eq!(
+            problems(9, "R 60000\nL 60000\nU 1\n"),
+            vec![at(2, 3, "the motions take more than 100000 steps")]
+        );
+        let input = "addx 9223372036854775807\naddx 1\n".repeat(60);
+        assert_eq!(problems(10, &input), vec![at(1, 6, "register X overflows")]);
+    }
+}
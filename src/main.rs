@@ -1,32 +1,470 @@
-use advent_of_code_2022_rust::{run_all, run_day};
-use clap::Parser;
-use std::time::Instant;
+use advent_of_code_2022_rust::answers::AnswerKey;
+use advent_of_code_2022_rust::bench::{
+    bench_days, bench_table, load_baseline, save_baseline, timed_days,
+};
+use advent_of_code_2022_rust::cache::CACHE_FILE;
+use advent_of_code_2022_rust::check::check_day;
+use advent_of_code_2022_rust::data::{load_raw, read_input, STDIN};
+use advent_of_code_2022_rust::error::AocError;
+use advent_of_code_2022_rust::inputgen;
+use advent_of_code_2022_rust::lint;
+use advent_of_code_2022_rust::output::{render, Format};
+use advent_of_code_2022_rust::puzzle::Puzzle;
+use advent_of_code_2022_rust::readme::update_readme;
+use advent_of_code_2022_rust::report::{variants_table, DayReport};
+use advent_of_code_2022_rust::scaffold;
+use advent_of_code_2022_rust::select::DaySelection;
+use advent_of_code_2022_rust::solutions::{self, DAYS};
+use advent_of_code_2022_rust::watch;
+use advent_of_code_2022_rust::{
+    overrunning_steps, run_all, run_day_on_input, run_day_variants, run_days, RunOptions,
+};
+use clap::{Parser, Subcommand};
+use std::panic;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
 /// Simple program to greet a person
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Data directory.
     #[arg(default_value_t = String::from("puzzle-input"))]
     data_dir: String,
+    /// Days to run: a day, a range or a list, e.g. `7`, `3..=7` or `1,5,9`.
     #[arg(short, long)]
-    day: Option<usize>,
+    day: Option<DaySelection>,
+    /// Only run this part of each day.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// List the registered days with their titles.
+    #[arg(long)]
+    list: bool,
+    /// Read the day's input from this file instead of the data directory, or from stdin
+    /// if it is `-`.
+    #[arg(short, long, requires = "day", conflicts_with = "verify")]
+    input: Option<String>,
+    /// Run the day on every input for it in the data directory (e.g. `10.txt` and
+    /// `10_ex1.txt`) and show the answers side by side.
+    #[arg(long, requires = "day", conflicts_with = "input")]
+    variants: bool,
+    /// Re-run the day whenever one of its inputs changes and show how the answers changed.
+    #[arg(long, requires = "day")]
+    watch: bool,
+    /// Check answers against the answers file in the data directory.
+    #[arg(long)]
+    verify: bool,
+    /// Number of threads to run the days and their parts on.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+    /// Time limit in seconds for each part. A part that overruns is reported as timed out.
+    #[arg(long, value_parser = parse_seconds)]
+    time_limit: Option<Duration>,
+    /// Time limit in seconds for a day: parsing its input and solving its parts, one after
+    /// the other.
+    #[arg(long, value_parser = parse_seconds)]
+    day_time_limit: Option<Duration>,
+    /// Reuse answers cached by earlier runs for unchanged inputs and solutions, and cache
+    /// new answers.
+    #[arg(long)]
+    cache: bool,
+    /// Solve every part even if its answer is cached, and replace the cached answers with
+    /// the new ones. Needs `--cache`.
+    #[arg(long, requires = "cache")]
+    refresh_cache: bool,
+    /// Output format of the results.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-fn main() {
-    let args = Args::parse();
-    let start = Instant::now();
-    match args.day {
-        Some(d) => {
-            println!("Running puzzle {}.", d);
-            run_day(&args.data_dir, &d);
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Time the parser and each part of the puzzles.
+    Bench {
+        /// Days to benchmark: a day, a range or a list, e.g. `7`, `3..=7` or `1,5,9`.
+        #[arg(short, long)]
+        day: Option<DaySelection>,
+        /// Number of times to run each phase.
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+        /// Save the results as a baseline file.
+        #[arg(long)]
+        save: Option<String>,
+        /// Compare the results against a saved baseline file.
+        #[arg(long)]
+        baseline: Option<String>,
+    },
+    /// Regenerate the progress table in the README from the registered days and the
+    /// answers verified against the answers file.
+    Readme {
+        /// Only check that the table is up to date, without writing the README.
+        #[arg(long)]
+        check: bool,
+    },
+    /// Add a new day: generate its module from a template and register it.
+    NewDay {
+        day: u32,
+        /// Puzzle title, e.g. "Distress Signal".
+        #[arg(long)]
+        title: String,
+    },
+    /// Print a random valid input for a day, made from a seed.
+    GenInput {
+        day: u32,
+        /// Seed of the generator: the same seed always gives the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Compare the solutions with simple reference solutions on generated inputs, and show
+    /// the first input each day disagrees on, minimized.
+    Check {
+        /// Days to check: a day, a range or a list, e.g. `7`, `3..=7` or `1,5,9`.
+        #[arg(short, long)]
+        day: Option<DaySelection>,
+        /// Only check this part of each day.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Number of generated inputs to check each day on.
+        #[arg(long, default_value_t = 100)]
+        seeds: u64,
+        /// Seed of the first generated input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Check a day's input without solving it, and list every problem found.
+    Lint {
+        /// Day of the input.
+        #[arg(short, long)]
+        day: u32,
+        /// Input file to check instead of the one in the data directory, or `-` for
+        /// standard input.
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+}
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    match arg.parse::<f64>() {
+        Ok(secs) if secs > 0.0 && secs.is_finite() => Ok(Duration::from_secs_f64(secs)),
+        _ => Err(format!(
+            "expected a positive number of seconds, got '{}'",
+            arg
+        )),
+    }
+}
+
+fn select_days(day: Option<DaySelection>) -> Result<Vec<&'static dyn Puzzle>, AocError> {
+    match day {
+        Some(selection) => selection
+            .days()
+            .iter()
+            .map(|d| solutions::get(*d).ok_or(AocError::UnknownDay(*d)))
+            .collect(),
+        None => Ok(DAYS.to_vec()),
+    }
+}
+
+fn list() {
+    for puzzle in DAYS.iter() {
+        println!("Day {}: {}", puzzle.day(), puzzle.title());
+    }
+}
+
+fn bench(
+    data_dir: &str,
+    day: Option<DaySelection>,
+    runs: usize,
+    save: Option<String>,
+    baseline: Option<String>,
+) -> Result<ExitCode, AocError> {
+    let baseline = match baseline {
+        Some(path) => Some(load_baseline(&path)?),
+        None => None,
+    };
+    let results = bench_days(data_dir, &select_days(day)?, runs)?;
+    println!("{}", bench_table(&results, baseline.as_ref()));
+    if let Some(path) = save {
+        save_baseline(&path, &timed_days(&results))?;
+        println!("Saved baseline to {}.", path);
+    }
+    match results.values().all(|r| r.is_ok()) {
+        true => Ok(ExitCode::SUCCESS),
+        false => Ok(ExitCode::FAILURE),
+    }
+}
+
+fn new_day(day: u32, title: &str) -> Result<(), AocError> {
+    let written = scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day, title)?;
+    for path in written.iter() {
+        println!("Wrote {}", path);
+    }
+    println!(
+        "Run `cargo fmt` and add the puzzle input as puzzle-input/{:02}.txt.",
+        day
+    );
+    Ok(())
+}
+
+fn gen_input(day: u32, seed: u64) -> Result<(), AocError> {
+    let input = inputgen::generate(day, seed).ok_or(AocError::NoGenerator(day))?;
+    print!("{}", input);
+    Ok(())
+}
+
+/// Print every problem in the input of a day, failing if there are any.
+fn lint_input(data_dir: &str, day: u32, input: Option<&str>) -> Result<ExitCode, AocError> {
+    lint::get(day).ok_or(AocError::NoLinter(day))?;
+    let text = match input {
+        Some(path) => read_input(path)?,
+        None => load_raw(data_dir, day, None)?,
+    };
+    let problems = lint::lint(day, &text)?;
+    for problem in problems.iter() {
+        println!("{}\n", problem);
+    }
+    match problems.len() {
+        0 => println!("Day {}: no problems found.", day),
+        1 => println!("Day {}: 1 problem found.", day),
+        n => println!("Day {}: {} problems found.", day, n),
+    }
+    Ok(match problems.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    })
+}
+
+/// Check the selected days against their reference solutions. Days without a reference or
+/// an input generator are reported and skipped. Panic messages are hidden while checking,
+/// since minimizing an input can make a solution panic many times.
+fn check(day: Option<DaySelection>, part: Option<u8>, seed: u64, seeds: u64) -> ExitCode {
+    let Some(end) = seed.checked_add(seeds) else {
+        eprintln!(
+            "--seed {} with --seeds {} goes past the largest seed, {}.",
+            seed,
+            seeds,
+            u64::MAX
+        );
+        return ExitCode::FAILURE;
+    };
+    let puzzles = match select_days(day) {
+        Ok(puzzles) => puzzles,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
+    };
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut agreed = true;
+    for puzzle in puzzles.iter() {
+        match check_day(puzzle.day(), seed..end, part) {
+            Ok(None) => println!("Day {}: agrees on {} inputs.", puzzle.day(), seeds),
+            Ok(Some(disagreement)) => {
+                println!("{}", disagreement);
+                agreed = false;
+            }
+            Err(e @ (AocError::NoReference(_) | AocError::NoGenerator(_))) => {
+                println!("Day {}: skipped. {}", puzzle.day(), e)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                agreed = false;
+            }
+        }
+    }
+    panic::set_hook(hook);
+    match agreed {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn readme(data_dir: &str, check: bool) -> Result<(), AocError> {
+    let key = AnswerKey::load(data_dir)?;
+    let mut reports = run_all(data_dir, &RunOptions::default());
+    reports.iter_mut().for_each(|r| key.verify(r));
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+    update_readme(&path, &reports, check)?;
+    match check {
+        true => println!("{} is up to date.", path.display()),
+        false => println!("Updated {}.", path.display()),
+    }
+    Ok(())
+}
+
+/// Run the selected days and verify their answers if a key is given.
+fn run_selected(
+    args: &Args,
+    options: &RunOptions,
+    answer_key: Option<&AnswerKey>,
+) -> Result<Vec<DayReport>, String> {
+    let mut reports = match &args.day {
+        Some(selection) => match (selection.single(), &args.input, args.variants) {
+            (Some(d), Some(path), _) => {
+                run_day_on_input(path, &(d as usize), options).map(|r| vec![r])
+            }
+            (Some(d), None, true) => run_day_variants(&args.data_dir, &(d as usize), options),
+            (None, Some(_), _) | (None, None, true) => {
+                return Err(String::from("--input and --variants need a single day."))
+            }
+            (_, None, false) => run_days(&args.data_dir, selection.days(), options),
+        },
+        None => Ok(run_all(&args.data_dir, options)),
+    }
+    .map_err(|e| e.to_string())?;
+    if let Some(key) = answer_key {
+        reports.iter_mut().for_each(|r| key.verify(r));
+    }
+    Ok(reports)
+}
+
+fn print_reports(args: &Args, reports: &[DayReport]) {
+    match args.format == Format::Text && args.variants {
+        true => println!("{}\n", variants_table(reports)),
+        false => println!("{}", render(reports, args.format)),
+    }
+}
+
+/// Re-run a single day whenever one of its inputs changes, showing how the answers changed.
+fn watch_day(args: &Args, options: &RunOptions, answer_key: Option<&AnswerKey>) -> ExitCode {
+    let day = match args.day.as_ref().and_then(|s| s.single()) {
+        Some(day) => day,
         None => {
-            println!("Running all puzzles.");
-            run_all(&args.data_dir);
+            eprintln!("--watch needs a single day.");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Stdin is used up by the first run, so later runs would solve an empty input.
+    if args.input.as_deref() == Some(STDIN) {
+        eprintln!("--watch cannot read the input from stdin, give a file with --input.");
+        return ExitCode::FAILURE;
+    }
+    let input = args.input.as_deref();
+    let mut previous: Option<Vec<DayReport>> = None;
+    watch::watch(
+        || watch::watched_files(&args.data_dir, day, input),
+        || {
+            println!("Running puzzle {}.", day);
+            match run_selected(args, options, answer_key) {
+                Ok(reports) => {
+                    print_reports(args, &reports);
+                    if let Some(previous) = &previous {
+                        println!("Changes since the last run:");
+                        for line in watch::answer_diff(previous, &reports) {
+                            println!("  {}", line);
+                        }
+                    }
+                    previous = Some(reports);
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+            match overrunning_steps() {
+                0 => {}
+                n => eprintln!("{} timed-out steps are still running in the background.", n),
+            }
+            println!("Watching for changes to the input, press Ctrl-C to stop.\n");
+        },
+    )
+}
+
+fn run(args: Args) -> ExitCode {
+    let answer_key = match args.verify {
+        true => match AnswerKey::load(&args.data_dir) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        false => None,
+    };
+
+    let options = RunOptions {
+        jobs: args.jobs as usize,
+        day_limit: args.day_time_limit,
+        part_limit: args.time_limit,
+        part: args.part,
+        cache: args
+            .cache
+            .then(|| Path::new(&args.data_dir).join(CACHE_FILE)),
+        recompute: args.refresh_cache,
+    };
+    if args.watch {
+        return watch_day(&args, &options, answer_key.as_ref());
+    }
+
+    let text = args.format == Format::Text;
+    if text {
+        match &args.day {
+            Some(selection) => match selection.single() {
+                Some(d) => println!("Running puzzle {}.", d),
+                None => println!("Running puzzles {:?}.", selection.days()),
+            },
+            None => println!("Running all puzzles."),
+        }
+    }
+    let start = Instant::now();
+    let reports = match run_selected(&args, &options, answer_key.as_ref()) {
+        Ok(reports) => reports,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
         }
     };
     let duration = start.elapsed();
-    print!("Done! 🎉");
-    println!(" -- Elapsed time: {:?}", duration);
+    print_reports(&args, &reports);
+    if text {
+        print!("Done! 🎉");
+        println!(" -- Elapsed time: {:?}", duration);
+    }
+
+    match reports.iter().all(|r| r.is_ok()) {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = Args::parse();
+    if args.list {
+        list();
+        return ExitCode::SUCCESS;
+    }
+    let result = match args.command.take() {
+        None => return run(args),
+        Some(Command::Bench {
+            day,
+            runs,
+            save,
+            baseline,
+        }) => match bench(&args.data_dir, day, runs, save, baseline) {
+            Ok(code) => return code,
+            Err(e) => Err(e),
+        },
+        Some(Command::Readme { check }) => readme(&args.data_dir, check),
+        Some(Command::NewDay { day, title }) => new_day(day, &title),
+        Some(Command::GenInput { day, seed }) => gen_input(day, seed),
+        Some(Command::Check {
+            day,
+            part,
+            seeds,
+            seed,
+        }) => return check(day, part, seed, seeds),
+        Some(Command::Lint { day, input }) => {
+            match lint_input(&args.data_dir, day, input.as_deref()) {
+                Ok(code) => return code,
+                Err(e) => Err(e),
+            }
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
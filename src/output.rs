@@ -0,0 +1,215 @@
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use crate::report::{summary_table, DayReport, Failure, PartReport};
+use serde::Serialize;
+use std::time::Duration;
+
+/// How to print the reports of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable answers followed by a summary table.
+    Text,
+    /// One JSON object per day.
+    Json,
+    /// One CSV row per day and part.
+    Csv,
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[derive(Serialize)]
+struct PartRecord<'a> {
+    part: u8,
+    answer: Option<&'a Answer>,
+    error: Option<String>,
+    time_ms: f64,
+    cached: bool,
+    verdict: Option<&'static str>,
+    expected: Option<&'a Answer>,
+}
+
+#[derive(Serialize)]
+struct DayRecord<'a> {
+    day: u32,
+    title: &'a str,
+    input: &'a str,
+    status: String,
+    error: Option<String>,
+    parse_time_ms: f64,
+    total_time_ms: f64,
+    parts: Vec<PartRecord<'a>>,
+}
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Fail { .. } => "fail",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+impl<'a> PartRecord<'a> {
+    fn new(part: &'a PartReport) -> Self {
+        PartRecord {
+            part: part.part,
+            answer: part.answer.as_ref().ok(),
+            error: part.answer.as_ref().err().map(Failure::summary),
+            time_ms: millis(part.elapsed),
+            cached: part.cached,
+            verdict: part.verdict.as_ref().map(verdict_name),
+            expected: match &part.verdict {
+                Some(Verdict::Fail { expected }) => Some(expected),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl<'a> DayRecord<'a> {
+    fn new(report: &'a DayReport) -> Self {
+        DayRecord {
+            day: report.day,
+            title: report.title,
+            input: &report.input,
+            status: report.status().to_string().to_lowercase(),
+            error: report.error.as_ref().map(Failure::summary),
+            parse_time_ms: millis(report.parse_time),
+            total_time_ms: millis(report.total_time()),
+            parts: report.parts.iter().map(PartRecord::new).collect(),
+        }
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_csv(reports: &[DayReport]) -> String {
+    let mut rows = vec![
+        "day,title,input,status,part,answer,error,verdict,expected,parse_time_ms,time_ms,cached"
+            .to_string(),
+    ];
+    for record in reports.iter().map(DayRecord::new) {
+        let day_fields = [
+            record.day.to_string(),
+            record.title.to_string(),
+            record.input.to_string(),
+            record.status.clone(),
+        ];
+        let parse_time = format!("{:.3}", record.parse_time_ms);
+        if record.parts.is_empty() {
+            let fields = [
+                "",
+                "",
+                record.error.as_deref().unwrap_or_default(),
+                "",
+                "",
+                &parse_time,
+                "",
+                "",
+            ];
+            rows.push(csv_row(&day_fields, &fields));
+        }
+        for part in record.parts.iter() {
+            let fields = [
+                &part.part.to_string(),
+                &part.answer.map(|a| a.to_string()).unwrap_or_default(),
+                part.error.as_deref().unwrap_or_default(),
+                part.verdict.unwrap_or_default(),
+                &part.expected.map(|a| a.to_string()).unwrap_or_default(),
+                &parse_time,
+                &format!("{:.3}", part.time_ms),
+                &part.cached.to_string(),
+            ];
+            rows.push(csv_row(&day_fields, &fields));
+        }
+    }
+    rows.join("\n")
+}
+
+fn csv_row(day_fields: &[String], fields: &[&str]) -> String {
+    day_fields
+        .iter()
+        .map(|f| f.as_str())
+        .chain(fields.iter().copied())
+        .map(csv_field)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Render the reports of a run in the requested format.
+pub fn render(reports: &[DayReport], format: Format) -> String {
+    match format {
+        Format::Text => {
+            let days = reports
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("{}\n\n{}\n", days, summary_table(reports))
+        }
+        Format::Json => {
+            let records = reports.iter().map(DayRecord::new).collect::<Vec<_>>();
+            serde_json::to_string_pretty(&records).expect("Reports serialize to JSON.")
+        }
+        Format::Csv => to_csv(reports),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{csv_field, render, Format};
+    use crate::solutions::get;
+    use crate::solve;
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("CMZ"), "CMZ");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("#.\n.#"), "\"#.\n.#\"");
+    }
+
+    #[test]
+    fn render_json() {
+        let reports = vec![solve(get(1).unwrap(), "1000\n2000\n\n3000")];
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&reports, Format::Json)).unwrap();
+        assert_eq!(json[0]["day"], 1);
+        assert_eq!(json[0]["status"], "passed");
+        assert_eq!(json[0]["parts"][0]["answer"], 3000);
+        assert_eq!(json[0]["parts"][1]["answer"], 6000);
+
+        let reports = vec![solve(get(2).unwrap(), "A Q")];
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&reports, Format::Json)).unwrap();
+        assert_eq!(
+            json[0]["error"],
+            "error: line 1, column 3: expected `X`, `Y` or `Z`"
+        );
+    }
+
+    #[test]
+    fn render_csv() {
+        let reports = vec![
+            solve(get(1).unwrap(), "1000\n2000\n\n3000"),
+            solve(get(2).unwrap(), "A Q"),
+        ];
+        let csv = render(&reports, Format::Csv);
+        let rows = csv.lines().collect::<Vec<_>>();
+        // The error of day 2 is on one line, without the input snippet.
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].split(',').count(), rows[1].split(',').count());
+        assert!(rows[1].starts_with("1,Calorie Counting,,passed,1,3000,,"));
+        assert!(rows[3].starts_with(
+            "2,Rock Paper Scissors,,failed,,,\"error: line 1, column 3: expected `X`, `Y` or `Z`\","
+        ));
+    }
+}
@@ -0,0 +1,403 @@
+//! Building blocks shared by the day parsers: lines and blank-line separated blocks that
+//! remember where they are in the input, integer lists, keyword templates and character
+//! grids. Every error is a [`Diagnostic`] pointing at the part of the input that is wrong.
+
+use crate::diagnostic::Diagnostic;
+use crate::grid::Grid;
+use std::any::type_name;
+use std::str::FromStr;
+
+/// A piece of one input line, with the position of its first character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub text: &'a str,
+    /// The whole input line the span is part of.
+    pub source: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// A span covering a whole line of the input.
+    pub fn new(line: usize, source: &'a str) -> Self {
+        Span {
+            line,
+            column: 1,
+            text: source,
+            source,
+        }
+    }
+
+    /// An error underlining the span, or pointing just after it if it is empty.
+    pub fn error(&self, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            line: self.line,
+            column: self.column,
+            width: self.text.chars().count().max(1),
+            source_line: self.source.trim_end().to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// The part of the span between two byte offsets.
+    fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        Span {
+            column: self.column + self.text[..start].chars().count(),
+            text: &self.text[start..end],
+            ..*self
+        }
+    }
+
+    /// The word starting at a byte offset, or an empty span there if there is none.
+    fn word_at(&self, at: usize) -> Span<'a> {
+        let end = self.text[at..]
+            .find(char::is_whitespace)
+            .map_or(self.text.len(), |n| at + n);
+        self.slice(at, end)
+    }
+
+    /// The part of the span from a byte offset to its end.
+    fn rest(&self, start: usize) -> Span<'a> {
+        self.slice(start, self.text.len())
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.slice(start, self.text.trim_end().len().max(start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// The characters of the span, each with its own position.
+    pub fn chars(self) -> impl Iterator<Item = (Span<'a>, char)> {
+        self.text
+            .char_indices()
+            .map(move |(i, c)| (self.slice(i, i + c.len_utf8()), c))
+    }
+
+    /// The runs of non-whitespace characters of the span.
+    pub fn words(self) -> impl Iterator<Item = Span<'a>> {
+        self.split(char::is_whitespace)
+    }
+
+    /// The non-empty pieces of the span between characters matching `separator`.
+    fn split(self, separator: impl Fn(char) -> bool) -> impl Iterator<Item = Span<'a>> {
+        let mut start = 0;
+        self.text
+            .char_indices()
+            .chain([(self.text.len(), ' ')])
+            .filter_map(move |(i, c)| match separator(c) || i == self.text.len() {
+                true => {
+                    let piece = self.slice(start, i);
+                    start = i + c.len_utf8();
+                    Some(piece)
+                }
+                false => None,
+            })
+            .filter(|piece| !piece.is_empty())
+    }
+
+    /// Parse the span, ignoring surrounding whitespace.
+    pub fn parse<T: FromStr>(&self) -> Result<T, Diagnostic> {
+        let span = self.trim();
+        span.text.parse::<T>().map_err(|_| {
+            let what = type_name::<T>().rsplit("::").next().unwrap_or_default();
+            match span.is_empty() {
+                true => span.error(format!("expected {}", what)),
+                false => span.error(format!("could not parse `{}` as {}", span.text, what)),
+            }
+        })
+    }
+
+    /// Parse a list of values separated by commas and/or whitespace, such as `79, 98`.
+    pub fn ints<T: FromStr>(&self) -> Result<Vec<T>, Diagnostic> {
+        self.split(|c| c.is_whitespace() || c == ',')
+            .map(|piece| piece.parse())
+            .collect()
+    }
+
+    /// Match the span against a template like `move {} from {} to {}` and return the `N`
+    /// captured fields. Leading whitespace is skipped, and whitespace in the template
+    /// matches any amount of whitespace. A `{}` captures up to the next whitespace or the
+    /// next character of the template, except the last one of the template, which captures
+    /// the rest of the line.
+    ///
+    /// # Panics
+    /// If the template does not have exactly `N` placeholders.
+    pub fn fields<const N: usize>(&self, template: &str) -> Result<[Span<'a>; N], Diagnostic> {
+        let pieces = template.split("{}").collect::<Vec<_>>();
+        assert_eq!(
+            pieces.len() - 1,
+            N,
+            "template `{}` needs {} fields",
+            template,
+            N
+        );
+        let mut fields = Vec::with_capacity(N);
+        let mut at = self.skip_whitespace(0);
+        for (i, literal) in pieces.iter().enumerate() {
+            at = self.expect_literal(at, literal)?;
+            if i == N {
+                break;
+            }
+            let end = match pieces[i + 1].chars().next() {
+                _ if (i + 1 == N) & pieces[N].trim().is_empty() => {
+                    at = self.skip_whitespace(at);
+                    self.text.trim_end().len().max(at)
+                }
+                next => self.text[at..]
+                    .find(|c: char| c.is_whitespace() || Some(c) == next)
+                    .map_or(self.text.len(), |n| at + n),
+            };
+            let field = self.slice(at, end);
+            if field.is_empty() {
+                return Err(self.word_at(at).error("expected a value"));
+            }
+            at = end;
+            fields.push(field);
+        }
+        let rest = self.rest(at);
+        if !rest.text.trim().is_empty() {
+            return Err(rest
+                .trim()
+                .error(format!("unexpected `{}`", rest.text.trim())));
+        }
+        Ok(fields.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    /// The rest of the span after a keyword, such as the items after `Starting items:`.
+    pub fn after(&self, keyword: &str) -> Result<Span<'a>, Diagnostic> {
+        let at = self.expect_literal(self.skip_whitespace(0), keyword)?;
+        Ok(self.rest(at).trim())
+    }
+
+    /// The byte offset of the first non-whitespace character at or after `at`.
+    fn skip_whitespace(&self, at: usize) -> usize {
+        self.text.len() - self.text[at..].trim_start().len()
+    }
+
+    /// Match the literal part of a template starting at byte `at`, and return where the
+    /// match ends.
+    fn expect_literal(&self, mut at: usize, literal: &str) -> Result<usize, Diagnostic> {
+        for (i, expected) in literal.char_indices() {
+            if expected.is_whitespace() {
+                at = self.skip_whitespace(at);
+                continue;
+            }
+            match self.text[at..].chars().next() {
+                Some(c) if c == expected => at += c.len_utf8(),
+                _ => {
+                    let missing = literal[i..].split_whitespace().next().unwrap_or_default();
+                    return Err(self.word_at(at).error(format!("expected `{}`", missing)));
+                }
+            }
+        }
+        Ok(at)
+    }
+}
+
+/// The non-blank lines of the input, with the indentation common to all of them and
+/// trailing whitespace removed.
+pub fn lines(text: &str) -> Vec<Span<'_>> {
+    blocks(text).into_iter().flatten().collect()
+}
+
+/// The groups of non-blank lines of the input separated by blank lines, with the
+/// indentation common to all lines and trailing whitespace removed.
+pub fn blocks(text: &str) -> Vec<Vec<Span<'_>>> {
+    let indent = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+    let common = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(indent)
+        .min()
+        .unwrap_or(0);
+
+    let mut blocks = vec![Vec::new()];
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            blocks.push(Vec::new());
+            continue;
+        }
+        let start = line
+            .char_indices()
+            .nth(common)
+            .map_or(line.len(), |(j, _)| j);
+        let span = Span::new(i + 1, line).slice(start, line.trim_end().len().max(start));
+        blocks
+            .last_mut()
+            .unwrap_or_else(|| unreachable!())
+            .push(span);
+    }
+    blocks.retain(|block| !block.is_empty());
+    blocks
+}
+
+/// An empty span just after the last character of the input, for reporting what is
+/// missing from it.
+pub fn end(text: &str) -> Span<'_> {
+    let (line, last) = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .last()
+        .unwrap_or((0, ""));
+    let end = last.trim_end().len();
+    Span::new(line + 1, last).slice(end, end)
+}
+
+/// Parse a map with one character per cell. `cell` translates a character, and `expected`
+/// describes the characters it accepts for the error message.
+pub fn char_grid<T>(
+    text: &str,
+    expected: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, Diagnostic> {
+    let lines = lines(text);
+    let width = lines
+        .first()
+        .map_or(0, |line| line.trim().text.chars().count());
+    let mut rows = Vec::with_capacity(lines.len());
+    for line in lines.iter().map(|line| line.trim()) {
+        let mut row = Vec::with_capacity(width);
+        for (span, c) in line.chars() {
+            if row.len() == width {
+                return Err(span.error(format!("row is longer than the first, {} cells", width)));
+            }
+            row.push(cell(c).ok_or_else(|| span.error(format!("expected {}", expected)))?);
+        }
+        if row.len() < width {
+            let end = line.rest(line.text.len());
+            return Err(end.error(format!("row is shorter than the first, {} cells", width)));
+        }
+        rows.push(row);
+    }
+    Grid::from_rows(rows).map_err(|_| end(text).error(format!("expected a map of {}", expected)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diagnostic::Diagnostic;
+    use crate::parsing::{blocks, char_grid, end, lines, Span};
+
+    const EXAMPLE_1: &str = "
+    Monkey 0:
+      Starting items: 79, 98
+
+    Monkey 1:
+      Starting items: 54
+    ";
+
+    /// Line, column, underlined text and message of a diagnostic.
+    fn problem<T>(result: Result<T, Diagnostic>) -> (usize, usize, String, String) {
+        let Err(d) = result else {
+            panic!("expected a diagnostic");
+        };
+        let underlined = d
+            .source_line
+            .chars()
+            .skip(d.column - 1)
+            .take(d.width)
+            .collect();
+        (d.line, d.column, underlined, d.message)
+    }
+
+    fn at(
+        line: usize,
+        column: usize,
+        underlined: &str,
+        message: &str,
+    ) -> (usize, usize, String, String) {
+        (line, column, underlined.to_string(), message.to_string())
+    }
+
+    #[test]
+    fn lines_and_blocks() {
+        let blocks = blocks(EXAMPLE_1);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1][0].text, "Monkey 1:");
+        assert_eq!((blocks[1][0].line, blocks[1][0].column), (5, 5));
+        assert_eq!(blocks[1][0].source, "    Monkey 1:");
+        assert_eq!(blocks[1][1].text, "  Starting items: 54");
+        assert_eq!(lines(EXAMPLE_1).len(), 4);
+        assert!(lines(" \n\n").is_empty());
+        let end = end(EXAMPLE_1);
+        assert_eq!((end.line, end.column, end.text), (6, 25, ""));
+    }
+
+    #[test]
+    fn words_and_ints() {
+        let words = Span::new(1, " ab  cd").words().collect::<Vec<_>>();
+        assert_eq!(words.len(), 2);
+        assert_eq!((words[1].text, words[1].column), ("cd", 6));
+        assert_eq!(
+            Span::new(1, "79, 98,-3 ").ints::<i32>(),
+            Ok(vec![79, 98, -3])
+        );
+        assert_eq!(
+            problem(Span::new(1, "79, x8").ints::<u8>()),
+            at(1, 5, "x8", "could not parse `x8` as u8")
+        );
+        assert_eq!(
+            problem(Span::new(1, " ").parse::<u8>()),
+            at(1, 2, "", "expected u8")
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let line = Span::new(3, "move 1 from 2  to 3");
+        let [n, from, to] = line.fields("move {} from {} to {}").unwrap();
+        assert_eq!((n.text, from.text, to.text), ("1", "2", "3"));
+        assert_eq!((to.line, to.column), (3, 19));
+        let [a, b, c, d] = Span::new(1, "2-4,6-8").fields("{}-{},{}-{}").unwrap();
+        assert_eq!([a.text, b.text, c.text, d.text], ["2", "4", "6", "8"]);
+        let [id] = Span::new(1, "Monkey 12:").fields("Monkey {}:").unwrap();
+        assert_eq!(id.parse::<usize>(), Ok(12));
+        let [items] = Span::new(1, "Starting items: 1, 2")
+            .fields("Starting items: {}")
+            .unwrap();
+        assert_eq!(items.text, "1, 2");
+        let none = Span::new(1, "  Starting items: ")
+            .after("Starting items:")
+            .unwrap();
+        assert_eq!((none.text, none.column), ("", 19));
+
+        assert_eq!(
+            problem(line.fields::<3>("move {} to {} from {}")),
+            at(3, 8, "from", "expected `to`")
+        );
+        assert_eq!(
+            problem(Span::new(1, "move 1 from 2 to").fields::<3>("move {} from {} to {}")),
+            at(1, 17, "", "expected a value")
+        );
+        assert_eq!(
+            problem(Span::new(1, "Monkey 1: x y").fields::<1>("Monkey {}:")),
+            at(1, 11, "x y", "unexpected `x y`")
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let grid = char_grid("\n  12\n  34\n", "a digit", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid.render(|d| char::from_digit(*d, 10).unwrap()), "12\n34");
+        assert_eq!(
+            problem(char_grid("12\n3x", "a digit", |c| c.to_digit(10))),
+            at(2, 2, "x", "expected a digit")
+        );
+        assert_eq!(
+            problem(char_grid("12\n3", "a digit", |c| c.to_digit(10))),
+            at(2, 2, "", "row is shorter than the first, 2 cells")
+        );
+        assert_eq!(
+            problem(char_grid("12\n345", "a digit", |c| c.to_digit(10))),
+            at(2, 3, "5", "row is longer than the first, 2 cells")
+        );
+        assert_eq!(
+            problem(char_grid("", "a digit", |c| c.to_digit(10))),
+            at(1, 1, "", "expected a map of a digit")
+        );
+    }
+}
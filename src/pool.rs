@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Apply `f` to every item on up to `jobs` worker threads. The results are returned in the
+/// order of the items, whichever thread finished first.
+pub fn parallel_map<T, R, F>(jobs: usize, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results = (0..items.len()).map(|_| None).collect::<Vec<_>>();
+    thread::scope(|s| {
+        let workers = (0..jobs)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(i) {
+                            Some(item) => done.push((i, f(item))),
+                            None => return done,
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            match worker.join() {
+                Ok(done) => done.into_iter().for_each(|(i, r)| results[i] = Some(r)),
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|r| r.expect("Every item is taken by a worker."))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pool::parallel_map;
+
+    #[test]
+    fn results_in_item_order() {
+        let items = (0..100).collect::<Vec<u64>>();
+        let squares = items.iter().map(|x| x * x).collect::<Vec<_>>();
+        for jobs in [0, 1, 4, 200] {
+            assert_eq!(parallel_map(jobs, &items, |x| x * x), squares);
+        }
+        assert!(parallel_map(4, &Vec::<u64>::new(), |x| *x).is_empty());
+    }
+}
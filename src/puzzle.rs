@@ -0,0 +1,70 @@
+use crate::answer::Answer;
+use crate::error::AocError;
+use std::any::Any;
+
+/// A day's puzzle: parse the input once, then solve each part from the parsed input.
+pub trait Solution {
+    /// Day of the advent calendar.
+    const DAY: u32;
+    /// Puzzle title (without the "Day N" prefix).
+    const TITLE: &'static str;
+    /// Bump when a change to the solution could change its answers, so that answers cached
+    /// by earlier versions are not reused.
+    const VERSION: u32 = 1;
+
+    type Input: Send + Sync + 'static;
+    type Output1: Into<Answer>;
+    type Output2: Into<Answer>;
+    type Error: Into<AocError>;
+
+    fn parse(input_data: &str) -> Result<Self::Input, Self::Error>;
+    fn part_1(input: &Self::Input) -> Result<Self::Output1, Self::Error>;
+    fn part_2(input: &Self::Input) -> Result<Self::Output2, Self::Error>;
+}
+
+/// Parsed puzzle input with its concrete type erased.
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+pub type PuzzleResult<T> = Result<T, AocError>;
+
+/// Object-safe view of a [`Solution`] so that all days can live in one registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u32;
+    fn title(&self) -> &'static str;
+    fn version(&self) -> u32;
+    fn parse(&self, input_data: &str) -> PuzzleResult<ParsedInput>;
+    fn part_1(&self, input: &ParsedInput) -> PuzzleResult<Answer>;
+    fn part_2(&self, input: &ParsedInput) -> PuzzleResult<Answer>;
+}
+
+fn downcast<S: Solution>(input: &ParsedInput) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .unwrap_or_else(|| panic!("Input was not parsed by day {}.", S::DAY))
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn version(&self) -> u32 {
+        S::VERSION
+    }
+
+    fn parse(&self, input_data: &str) -> PuzzleResult<ParsedInput> {
+        Ok(Box::new(S::parse(input_data).map_err(Into::into)?))
+    }
+
+    fn part_1(&self, input: &ParsedInput) -> PuzzleResult<Answer> {
+        Ok(S::part_1(downcast::<S>(input)).map_err(Into::into)?.into())
+    }
+
+    fn part_2(&self, input: &ParsedInput) -> PuzzleResult<Answer> {
+        Ok(S::part_2(downcast::<S>(input)).map_err(Into::into)?.into())
+    }
+}
@@ -0,0 +1,147 @@
+use crate::answers::Verdict;
+use crate::report::DayReport;
+use crate::scaffold::insert_in_order;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReadmeError {
+    #[error("Could not read {}: {}", .0, .1)]
+    Reading(String, std::io::Error),
+    #[error("Could not write {}: {}", .0, .1)]
+    Writing(String, std::io::Error),
+    #[error("Could not find the progress table in {}.", .0)]
+    NoTable(String),
+    #[error("The progress table in {} is out of date.", .0)]
+    OutOfDate(String),
+}
+
+const TABLE_HEADER: [&str; 2] = [
+    "| Day | Code                                                     | Stars |",
+    "| ---:| -------------------------------------------------------- | ----- |",
+];
+
+/// Row of the README progress table for a day.
+pub fn readme_row(day: u32, stars: usize) -> String {
+    // Each star is two chars (with its variation selector), padded as in the existing rows.
+    let stars = "⭐️".repeat(stars);
+    let padding = " ".repeat(6_usize.saturating_sub(stars.chars().count()));
+    format!(
+        "| {:<3} | [src/solutions/day{:02}.rs](src/solutions/day{:02}.rs) | {}{} |",
+        day, day, day, stars, padding
+    )
+}
+
+/// Add a row for the day to the README progress table, replacing a commented-out row.
+pub fn add_readme_row(readme: &str, day: u32) -> Option<String> {
+    let commented = format!("<!-- | {:<3} |", day);
+    if readme.lines().any(|line| line.starts_with(&commented)) {
+        let lines = readme
+            .lines()
+            .map(|line| match line.starts_with(&commented) {
+                true => readme_row(day, 0),
+                false => line.to_string(),
+            })
+            .collect::<Vec<_>>();
+        return Some(lines.join("\n") + "\n");
+    }
+    insert_in_order(readme, day, "| ", 0, &[readme_row(day, 0)])
+}
+
+/// Progress table with a row per day and a star for each part whose answer was verified.
+pub fn progress_table(reports: &[DayReport]) -> String {
+    let rows = reports.iter().map(|report| {
+        let stars = report
+            .parts
+            .iter()
+            .filter(|p| p.verdict == Some(Verdict::Pass))
+            .count();
+        readme_row(report.day, stars)
+    });
+    TABLE_HEADER
+        .iter()
+        .map(|line| line.to_string())
+        .chain(rows)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace the progress table in the README, including any commented-out rows.
+pub fn replace_table(readme: &str, table: &str) -> Option<String> {
+    let lines = readme.lines().collect::<Vec<_>>();
+    let start = lines.iter().position(|line| *line == TABLE_HEADER[0])?;
+    let len = lines[start..]
+        .iter()
+        .take_while(|line| line.starts_with('|') | line.starts_with("<!-- |"))
+        .count();
+    let mut updated = lines[..start].to_vec();
+    updated.push(table);
+    updated.extend(&lines[start + len..]);
+    Some(updated.join("\n") + "\n")
+}
+
+/// Regenerate the progress table in the README at `path` from verified reports. With
+/// `check`, the README is not written and an error is returned if it is out of date.
+pub fn update_readme(path: &Path, reports: &[DayReport], check: bool) -> Result<(), ReadmeError> {
+    let name = path.display().to_string();
+    let readme = fs::read_to_string(path).map_err(|e| ReadmeError::Reading(name.clone(), e))?;
+    let updated = replace_table(&readme, &progress_table(reports))
+        .ok_or_else(|| ReadmeError::NoTable(name.clone()))?;
+    match (check, updated == readme) {
+        (_, true) => Ok(()),
+        (true, false) => Err(ReadmeError::OutOfDate(name)),
+        (false, false) => fs::write(path, updated).map_err(|e| ReadmeError::Writing(name, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Verdict;
+    use crate::readme::{add_readme_row, progress_table, readme_row, replace_table};
+    use crate::solutions::get;
+    use crate::solve;
+
+    const README: &str = include_str!("../README.md");
+
+    #[test]
+    fn readme_rows() {
+        assert_eq!(
+            readme_row(7, 2),
+            "| 7   | [src/solutions/day07.rs](src/solutions/day07.rs) | ⭐️⭐️   |"
+        );
+        let table = format!("{}\n{}\n", readme_row(1, 2), readme_row(3, 2));
+        let updated = add_readme_row(&table, 2).unwrap();
+        assert_eq!(updated.lines().nth(1), Some(readme_row(2, 0).as_str()));
+        assert!(add_readme_row(README, 25)
+            .unwrap()
+            .contains(&readme_row(25, 0)));
+    }
+
+    #[test]
+    fn table_from_reports() {
+        let mut report = solve(get(1).unwrap(), "1000\n2000\n\n3000");
+        report.parts[0].verdict = Some(Verdict::Pass);
+        report.parts[1].verdict = Some(Verdict::Unknown);
+        let table = progress_table(&[report]);
+        assert_eq!(table.lines().count(), 3);
+        assert_eq!(table.lines().last(), Some(readme_row(1, 1).as_str()));
+    }
+
+    #[test]
+    fn replace_readme_table() {
+        let readme = format!(
+            "# Title\n\n{}\n{}\n{}\n<!-- {} -->\n\n## Setup\n",
+            progress_table(&[]),
+            readme_row(1, 2),
+            readme_row(2, 1),
+            readme_row(3, 2)
+        );
+        let table = format!("{}\n{}", progress_table(&[]), readme_row(1, 1));
+        assert_eq!(
+            replace_table(&readme, &table).unwrap(),
+            format!("# Title\n\n{}\n\n## Setup\n", table)
+        );
+        assert!(replace_table("# Title\n", &table).is_none());
+    }
+}
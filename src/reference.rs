@@ -0,0 +1,655 @@
+//! Straightforward reference solutions to check the real ones against. They simulate each
+//! puzzle the slow and obvious way, without the shortcuts of the solutions, and return `None`
+//! for input that is not a valid puzzle input so that answers are only compared where the
+//! puzzle defines them.
+
+use crate::answer::Answer;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Reference answer to a part of a day's puzzle, or `None` if the input is not valid.
+pub type Reference = fn(&str, u8) -> Option<Answer>;
+
+/// Day 1: total the calories of each elf.
+fn calories(input: &str, part: u8) -> Option<Answer> {
+    let mut totals = input
+        .trim()
+        .split("\n\n")
+        .map(|block| block.lines().map(|l| l.trim().parse::<u32>().ok()).sum())
+        .collect::<Option<Vec<u32>>>()?;
+    totals.sort();
+    totals.reverse();
+    match part {
+        1 => Some(Answer::from(totals[0])),
+        _ => Some(Answer::from(totals.iter().take(3).sum::<u32>())),
+    }
+}
+
+/// Day 2: score every round from a table of all nine of them.
+fn rock_paper_scissors(input: &str, part: u8) -> Option<Answer> {
+    let mut score = 0;
+    for line in input.trim().lines() {
+        score += match (part, line.trim()) {
+            // The second column is the shape to play.
+            (1, "A X") => 1 + 3,
+            (1, "A Y") => 2 + 6,
+            (1, "A Z") => 3,
+            (1, "B X") => 1,
+            (1, "B Y") => 2 + 3,
+            (1, "B Z") => 3 + 6,
+            (1, "C X") => 1 + 6,
+            (1, "C Y") => 2,
+            (1, "C Z") => 3 + 3,
+            // The second column is how the round must end.
+            (_, "A X") => 3,
+            (_, "A Y") => 1 + 3,
+            (_, "A Z") => 2 + 6,
+            (_, "B X") => 1,
+            (_, "B Y") => 2 + 3,
+            (_, "B Z") => 3 + 6,
+            (_, "C X") => 2,
+            (_, "C Y") => 3 + 3,
+            (_, "C Z") => 1 + 6,
+            _ => return None,
+        };
+    }
+    Some(Answer::from(score))
+}
+
+fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// The one item in all of the lists, or `None` if there is not exactly one.
+fn only_common_item(lists: &[&[char]]) -> Option<char> {
+    let common = lists[0]
+        .iter()
+        .filter(|item| lists.iter().all(|list| list.contains(item)))
+        .collect::<HashSet<_>>();
+    match common.len() {
+        1 => common.into_iter().next().copied(),
+        _ => None,
+    }
+}
+
+/// Day 3: look for items in both halves of a rucksack or in all three rucksacks of a group.
+fn rucksacks(input: &str, part: u8) -> Option<Answer> {
+    let rucksacks = input
+        .lines()
+        .map(|l| l.trim().chars().collect::<Vec<_>>())
+        .filter(|r| !r.is_empty())
+        .collect::<Vec<_>>();
+    let mut total = 0;
+    match part {
+        1 => {
+            for rucksack in rucksacks.iter() {
+                if rucksack.len() % 2 == 1 {
+                    return None;
+                }
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                total += priority(only_common_item(&[first, second])?)?;
+            }
+        }
+        _ => {
+            if rucksacks.len() % 3 != 0 {
+                return None;
+            }
+            for group in rucksacks.chunks(3) {
+                total += priority(only_common_item(&[&group[0], &group[1], &group[2]])?)?;
+            }
+        }
+    }
+    Some(Answer::from(total))
+}
+
+fn section_range(range: &str) -> Option<HashSet<u32>> {
+    let (from, to) = range.split_once('-')?;
+    let (from, to) = (from.parse::<u32>().ok()?, to.parse::<u32>().ok()?);
+    match from <= to {
+        true => Some((from..=to).collect()),
+        false => None,
+    }
+}
+
+/// Day 4: compare the sets of sections of each pair.
+fn section_pairs(input: &str, part: u8) -> Option<Answer> {
+    let mut count = 0;
+    for line in input.trim().lines() {
+        let (first, second) = line.trim().split_once(',')?;
+        let (first, second) = (section_range(first)?, section_range(second)?);
+        let counts = match part {
+            1 => first.is_subset(&second) | second.is_subset(&first),
+            _ => !first.is_disjoint(&second),
+        };
+        count += counts as u32;
+    }
+    Some(Answer::from(count))
+}
+
+/// Day 5: move crates one at a time, or in blocks with the newer crane. Every stack must
+/// start with a crate in the bottom row and end with at least one crate.
+fn crate_stacks(input: &str, part: u8) -> Option<Answer> {
+    let (drawing, moves) = input.trim_matches('\n').split_once("\n\n")?;
+    let mut rows = drawing.lines().rev();
+    let numbers = rows.next()?.split_whitespace().collect::<Vec<_>>();
+    let numbered = numbers
+        .iter()
+        .enumerate()
+        .all(|(i, n)| *n == (i + 1).to_string());
+    if numbers.is_empty() | (numbers.len() > 9) | !numbered {
+        return None;
+    }
+
+    let mut stacks = vec![Vec::new(); numbers.len()];
+    for (level, row) in rows.enumerate() {
+        let row = row.chars().collect::<Vec<_>>();
+        if row.len() > 4 * stacks.len() {
+            return None;
+        }
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            let start = (4 * i).min(row.len());
+            match &row[start..(start + 3).min(row.len())] {
+                ['[', c, ']'] if c.is_ascii_uppercase() & (stack.len() == level) => stack.push(*c),
+                cell if cell.iter().all(|c| *c == ' ') => (),
+                _ => return None,
+            }
+        }
+    }
+    if stacks.iter().any(|s| s.is_empty()) {
+        return None;
+    }
+
+    for line in moves.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        let (n, from, to) = match words[..] {
+            ["move", n, "from", from, "to", to] => (
+                n.parse::<usize>().ok()?,
+                from.parse::<usize>().ok()?.checked_sub(1)?,
+                to.parse::<usize>().ok()?.checked_sub(1)?,
+            ),
+            _ => return None,
+        };
+        if (from >= stacks.len()) | (to >= stacks.len()) || n > stacks[from].len() {
+            return None;
+        }
+        let mut lifted = Vec::new();
+        for _ in 0..n {
+            lifted.push(stacks[from].pop()?);
+        }
+        if part == 2 {
+            lifted.reverse();
+        }
+        stacks[to].extend(lifted);
+    }
+    let tops = stacks
+        .iter()
+        .map(|s| s.last().copied())
+        .collect::<Option<String>>()?;
+    Some(Answer::from(tops))
+}
+
+/// Day 6: check every window for repeated characters.
+fn data_stream(input: &str, part: u8) -> Option<Answer> {
+    let stream = input.trim().chars().collect::<Vec<_>>();
+    if stream.iter().any(|c| c.is_whitespace()) {
+        return None;
+    }
+    let size = match part {
+        1 => 4,
+        _ => 14,
+    };
+    let distinct =
+        |window: &[char]| (0..window.len()).all(|i| (0..i).all(|j| window[i] != window[j]));
+    (size..=stream.len())
+        .find(|end| distinct(&stream[end - size..*end]))
+        .map(Answer::from)
+}
+
+/// Day 7: add each file's size to every directory on its path. Only listed directories can
+/// be entered, and for part 2 the disk must be between 40 and 70 million units full.
+fn terminal_output(input: &str, part: u8) -> Option<Answer> {
+    let mut lines = input.trim().lines().map(|l| l.trim());
+    if lines.next()? != "$ cd /" {
+        return None;
+    }
+    let mut cwd: Vec<&str> = Vec::new();
+    let mut dirs = HashSet::from([Vec::new()]);
+    let mut files = HashMap::new();
+    for line in lines.filter(|l| !l.is_empty()) {
+        match line.split(' ').collect::<Vec<_>>()[..] {
+            ["$", "ls"] => (),
+            ["$", "cd", ".."] => {
+                cwd.pop()?;
+            }
+            ["$", "cd", name] => {
+                cwd.push(name);
+                if !dirs.contains(&cwd) {
+                    return None;
+                }
+            }
+            ["dir", name] => {
+                let mut path = cwd.clone();
+                path.push(name);
+                if files.contains_key(&path) {
+                    return None;
+                }
+                dirs.insert(path);
+            }
+            [size, name] => {
+                let size = size.parse::<usize>().ok().filter(|s| *s > 0)?;
+                let mut path = cwd.clone();
+                path.push(name);
+                if dirs.contains(&path) | files.get(&path).is_some_and(|s| *s != size) {
+                    return None;
+                }
+                files.insert(path, size);
+            }
+            _ => return None,
+        }
+    }
+
+    let sizes = dirs
+        .iter()
+        .map(|dir| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(dir))
+                .map(|(_, size)| size)
+                .sum::<usize>()
+        })
+        .collect::<Vec<_>>();
+    match part {
+        1 => Some(Answer::from(
+            sizes.iter().filter(|s| **s <= 100_000).sum::<usize>(),
+        )),
+        _ => {
+            let used = files.values().sum::<usize>();
+            if !(40_000_000..=70_000_000).contains(&used) {
+                return None;
+            }
+            sizes
+                .into_iter()
+                .filter(|s| *s >= used - 40_000_000)
+                .min()
+                .map(Answer::from)
+        }
+    }
+}
+
+/// Day 8: look along all four directions from every tree, without stopping early.
+fn tree_grid(input: &str, part: u8) -> Option<Answer> {
+    let grid = input
+        .trim()
+        .lines()
+        .map(|l| l.trim().chars().map(|c| c.to_digit(10)).collect())
+        .collect::<Option<Vec<Vec<u32>>>>()?;
+    let cols = grid.first()?.len();
+    if (cols == 0) | grid.iter().any(|row| row.len() != cols) {
+        return None;
+    }
+
+    let mut visible = 0;
+    let mut best_score = 0;
+    for (r, row) in grid.iter().enumerate() {
+        for (c, height) in row.iter().enumerate() {
+            let lines_of_sight: [Vec<u32>; 4] = [
+                (0..c).rev().map(|c| grid[r][c]).collect(),
+                (c + 1..cols).map(|c| grid[r][c]).collect(),
+                (0..r).rev().map(|r| grid[r][c]).collect(),
+                (r + 1..grid.len()).map(|r| grid[r][c]).collect(),
+            ];
+            if lines_of_sight
+                .iter()
+                .any(|trees| trees.iter().all(|t| t < height))
+            {
+                visible += 1;
+            }
+            let score = lines_of_sight
+                .iter()
+                .map(|trees| match trees.iter().position(|t| t >= height) {
+                    Some(blocker) => blocker + 1,
+                    None => trees.len(),
+                })
+                .product::<usize>();
+            best_score = best_score.max(score);
+        }
+    }
+    match part {
+        1 => Some(Answer::from(visible)),
+        _ => Some(Answer::from(best_score)),
+    }
+}
+
+/// Day 9: move the head one step at a time and let every knot catch up after each step.
+fn rope_motions(input: &str, part: u8) -> Option<Answer> {
+    let length = match part {
+        1 => 2,
+        _ => 10,
+    };
+    let mut knots = vec![(0i64, 0i64); length];
+    let mut visited = HashSet::from([(0, 0)]);
+    for line in input.trim().lines() {
+        let (direction, steps) = line.trim().split_once(' ')?;
+        let (dx, dy) = match direction {
+            "U" => (0, 1),
+            "D" => (0, -1),
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            _ => return None,
+        };
+        for _ in 0..steps.parse::<usize>().ok()? {
+            knots[0] = (knots[0].0 + dx, knots[0].1 + dy);
+            for i in 1..knots.len() {
+                let (x, y) = (knots[i - 1].0 - knots[i].0, knots[i - 1].1 - knots[i].1);
+                if (x.abs() > 1) | (y.abs() > 1) {
+                    knots[i] = (knots[i].0 + x.signum(), knots[i].1 + y.signum());
+                }
+            }
+            visited.insert(knots[knots.len() - 1]);
+        }
+    }
+    Some(Answer::from(visited.len()))
+}
+
+/// Day 10: record the register during every cycle, then read the signal and the screen off
+/// that record. The program may not run for longer than the 240 cycles the screen shows.
+fn cpu_program(input: &str, part: u8) -> Option<Answer> {
+    let mut x = 1;
+    let mut during = Vec::new();
+    for line in input.trim().lines() {
+        match line.trim().split(' ').collect::<Vec<_>>()[..] {
+            ["noop"] => during.push(x),
+            ["addx", v] => {
+                let v = v.parse::<i64>().ok()?;
+                during.extend([x, x]);
+                x += v;
+            }
+            _ => return None,
+        }
+    }
+    match part {
+        1 => Some(Answer::from(
+            [20, 60, 100, 140, 180, 220]
+                .iter()
+                .filter(|cycle| **cycle <= during.len())
+                .map(|cycle| *cycle as i64 * during[cycle - 1])
+                .sum::<i64>(),
+        )),
+        _ => {
+            if during.len() > 240 {
+                return None;
+            }
+            let rows = (0..6)
+                .map(|row| {
+                    (0..40)
+                        .map(|col| match during.get(40 * row + col) {
+                            Some(x) if (x - col as i64).abs() <= 1 => '#',
+                            _ => '.',
+                        })
+                        .collect::<String>()
+                })
+                .collect();
+            Some(Answer::Grid(rows))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: Vec<i128>,
+    operation: (String, String),
+    divisor: i128,
+    targets: (usize, usize),
+}
+
+impl Monkey {
+    fn inspect(&self, old: i128) -> Option<i128> {
+        let value = |operand: &str| match operand {
+            "old" => Some(old),
+            x => x.parse::<i128>().ok().filter(|x| *x >= 0),
+        };
+        let (op, operand) = &self.operation;
+        match op.as_str() {
+            "+" => Some(old + value(operand)?),
+            "*" => Some(old * value(operand)?),
+            _ => None,
+        }
+    }
+}
+
+fn last_number<T: std::str::FromStr>(line: &str, prefix: &str) -> Option<T> {
+    line.strip_prefix(prefix)?.parse().ok()
+}
+
+fn parse_monkey(id: usize, lines: &[&str]) -> Option<Monkey> {
+    if lines[0] != format!("Monkey {}:", id) {
+        return None;
+    }
+    let items = lines[1]
+        .strip_prefix("Starting items: ")?
+        .split(", ")
+        .map(|x| x.parse::<i128>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (op, operand) = lines[2]
+        .strip_prefix("Operation: new = old ")?
+        .split_once(' ')?;
+    Some(Monkey {
+        items,
+        operation: (op.to_string(), operand.to_string()),
+        divisor: last_number(lines[3], "Test: divisible by ").filter(|d| *d > 0)?,
+        targets: (
+            last_number(lines[4], "If true: throw to monkey ")?,
+            last_number(lines[5], "If false: throw to monkey ")?,
+        ),
+    })
+}
+
+/// Day 11: pass the items around one by one. In part 2 worry levels are kept modulo the
+/// product of the divisors after every single inspection.
+fn monkey_notes(input: &str, part: u8) -> Option<Answer> {
+    let lines = input
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>();
+    if lines.len() % 6 != 0 {
+        return None;
+    }
+    let mut monkeys = lines
+        .chunks(6)
+        .enumerate()
+        .map(|(id, lines)| parse_monkey(id, lines))
+        .collect::<Option<Vec<_>>>()?;
+    let n = monkeys.len();
+    let targets_valid = monkeys.iter().enumerate().all(|(id, m)| {
+        [m.targets.0, m.targets.1]
+            .iter()
+            .all(|t| (*t < n) & (*t != id))
+    });
+    if (n < 2) | !targets_valid {
+        return None;
+    }
+
+    let modulus = monkeys.iter().map(|m| m.divisor).product::<i128>();
+    let rounds = match part {
+        1 => 20,
+        _ => 10_000,
+    };
+    let mut inspected = vec![0usize; n];
+    for _ in 0..rounds {
+        for id in 0..n {
+            for old in std::mem::take(&mut monkeys[id].items) {
+                inspected[id] += 1;
+                let new = monkeys[id].inspect(old)?;
+                if new > isize::MAX as i128 {
+                    return None;
+                }
+                let new = match part {
+                    1 => new.div_euclid(3),
+                    _ => new % modulus,
+                };
+                let target = match new % monkeys[id].divisor {
+                    0 => monkeys[id].targets.0,
+                    _ => monkeys[id].targets.1,
+                };
+                monkeys[target].items.push(new);
+            }
+        }
+    }
+    inspected.sort();
+    Some(Answer::from(inspected[n - 1] * inspected[n - 2]))
+}
+
+/// Day 12: breadth-first search from the start, and for part 2 from every lowest square.
+fn height_map(input: &str, part: u8) -> Option<Answer> {
+    let grid = input
+        .trim()
+        .lines()
+        .map(|l| l.trim().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let squares = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, x)| ((r, c), *x)))
+        .collect::<Vec<_>>();
+    let find = |mark: char| {
+        let found = squares
+            .iter()
+            .filter(|(_, x)| *x == mark)
+            .collect::<Vec<_>>();
+        match found[..] {
+            [(at, _)] => Some(*at),
+            _ => None,
+        }
+    };
+    let (start, end) = (find('S')?, find('E')?);
+    let height = |(r, c): (usize, usize)| match grid[r][c] {
+        'S' => Some(0),
+        'E' => Some(25),
+        x @ 'a'..='z' => Some(x as u32 - 'a' as u32),
+        _ => None,
+    };
+    if squares.iter().any(|(at, _)| height(*at).is_none()) {
+        return None;
+    }
+
+    let steps_to_end = |from: (usize, usize)| {
+        let mut steps = HashMap::from([(from, 0)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some((r, c)) = queue.pop_front() {
+            if (r, c) == end {
+                return Some(steps[&end]);
+            }
+            let neighbours = [
+                (r.wrapping_sub(1), c),
+                (r + 1, c),
+                (r, c.wrapping_sub(1)),
+                (r, c + 1),
+            ];
+            for next in neighbours {
+                let on_map = grid.get(next.0).is_some_and(|row| next.1 < row.len());
+                if on_map && !steps.contains_key(&next) && height(next)? <= height((r, c))? + 1 {
+                    steps.insert(next, steps[&(r, c)] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    };
+    match part {
+        1 => steps_to_end(start).map(Answer::from),
+        _ => squares
+            .iter()
+            .filter(|(at, _)| height(*at) == Some(0))
+            .filter_map(|(at, _)| steps_to_end(*at))
+            .min()
+            .map(Answer::from),
+    }
+}
+
+/// Reference solutions by day.
+static REFERENCES: &[(u32, Reference)] = &[
+    (1, calories),
+    (2, rock_paper_scissors),
+    (3, rucksacks),
+    (4, section_pairs),
+    (5, crate_stacks),
+    (6, data_stream),
+    (7, terminal_output),
+    (8, tree_grid),
+    (9, rope_motions),
+    (10, cpu_program),
+    (11, monkey_notes),
+    (12, height_map),
+];
+
+/// Look up the reference solution for a day.
+pub fn get(day: u32) -> Option<Reference> {
+    REFERENCES
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, reference)| *reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::AnswerKey;
+    use crate::data::{input_name, load_raw};
+    use crate::reference::{get, REFERENCES};
+    use crate::solutions::DAYS;
+
+    #[test]
+    fn references_are_for_registered_days() {
+        let registered = DAYS.iter().map(|p| p.day()).collect::<Vec<_>>();
+        assert!(REFERENCES.iter().all(|(day, _)| registered.contains(day)));
+        assert!(get(25).is_none());
+    }
+
+    #[test]
+    fn references_solve_puzzle_inputs() {
+        let key = AnswerKey::load("puzzle-input").unwrap();
+        for puzzle in DAYS.iter() {
+            let Some(reference) = get(puzzle.day()) else {
+                continue;
+            };
+            let Ok(input) = load_raw("puzzle-input", puzzle.day(), None) else {
+                continue;
+            };
+            for part in [1, 2] {
+                let expected = key.expected(&input_name(puzzle.day(), None), part);
+                assert_eq!(
+                    reference(&input, part).as_ref(),
+                    expected,
+                    "day {} puzzle {}",
+                    puzzle.day(),
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_inputs_have_no_reference_answer() {
+        assert_eq!(get(2).unwrap()("A W", 1), None);
+        assert_eq!(get(3).unwrap()("abcabd", 1), None);
+        assert_eq!(
+            get(7).unwrap()(
+                "$ cd /
+$ cd a",
+                1
+            ),
+            None
+        );
+        assert_eq!(
+            get(12).unwrap()(
+                "SabE
+Sbcd",
+                1
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use std::fmt;
+use std::time::Duration;
+
+/// Why a step of a run did not produce a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Error(String),
+    Panicked(String),
+    /// The step ran past its time limit.
+    TimedOut(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "error: {}", e),
+            Failure::Panicked(msg) => write!(f, "panicked: {}", msg),
+            Failure::TimedOut(limit) => write!(f, "timed out after {:?}", limit),
+        }
+    }
+}
+
+impl Failure {
+    /// The failure on one line: parse diagnostics without the input line and caret.
+    pub fn summary(&self) -> String {
+        self.to_string()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// Overall state of a day's run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+    Panicked,
+    TimedOut,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Passed => write!(f, "passed"),
+            Status::Failed => write!(f, "FAILED"),
+            Status::Panicked => write!(f, "PANICKED"),
+            Status::TimedOut => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+/// Result of solving one part of a day's puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<Answer, Failure>,
+    pub elapsed: Duration,
+    /// Whether the answer was taken from the cache instead of being solved.
+    pub cached: bool,
+    /// Comparison with the expected answer, if the report was verified.
+    pub verdict: Option<Verdict>,
+}
+
+/// Result of running a day's puzzle: the answer to each part with timings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u32,
+    pub title: &'static str,
+    /// Name of the input, e.g. `10_ex1` for `puzzle-input/10_ex1.txt`.
+    pub input: String,
+    /// Failure loading or parsing the input. The parts are not run if this is set.
+    pub error: Option<Failure>,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn new(day: u32, title: &'static str) -> Self {
+        DayReport {
+            day,
+            title,
+            input: String::new(),
+            error: None,
+            parse_time: Duration::default(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Whether the input was parsed and every part produced an answer that was not
+    /// rejected by verification.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+            & self
+                .parts
+                .iter()
+                .all(|p| p.answer.is_ok() & !matches!(p.verdict, Some(Verdict::Fail { .. })))
+    }
+
+    /// Time spent parsing and solving all parts.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    fn failures(&self) -> impl Iterator<Item = &Failure> {
+        self.error
+            .iter()
+            .chain(self.parts.iter().filter_map(|p| p.answer.as_ref().err()))
+    }
+
+    pub fn status(&self) -> Status {
+        if self.failures().any(|f| matches!(f, Failure::Panicked(_))) {
+            Status::Panicked
+        } else if self.failures().any(|f| matches!(f, Failure::TimedOut(_))) {
+            Status::TimedOut
+        } else if self.is_ok() {
+            Status::Passed
+        } else {
+            Status::Failed
+        }
+    }
+
+    /// Short description of the first thing that went wrong, if anything did.
+    pub fn problem(&self) -> Option<String> {
+        if let Some(failure) = self.failures().next() {
+            return failure.to_string().lines().next().map(String::from);
+        }
+        self.parts.iter().find_map(|p| match &p.verdict {
+            Some(Verdict::Fail { expected }) => Some(format!(
+                "puzzle {}: wrong answer, expected {}",
+                p.part,
+                expected.to_string().replace('\n', " ")
+            )),
+            _ => None,
+        })
+    }
+}
+
+/// Table with one row per day giving its status, run time and first problem.
+pub fn summary_table(reports: &[DayReport]) -> String {
+    let mut table = vec![
+        format!("{:>4} | {:<8} | {:>12} | Message", "Day", "Status", "Time"),
+        format!("{:-<4}-+-{:-<8}-+-{:-<12}-+-{:-<7}", "", "", "", ""),
+    ];
+    for report in reports.iter() {
+        let row = format!(
+            "{:>4} | {:<8} | {:>12} | {}",
+            report.day,
+            report.status().to_string(),
+            format!("{:.2?}", report.total_time()),
+            report.problem().unwrap_or_default()
+        );
+        table.push(row.trim_end().to_string());
+    }
+    table.join("\n")
+}
+
+impl fmt::Display for PartReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.answer {
+            Ok(x) if x.is_multiline() => write!(f, " Puzzle {}: \n{}", self.part, x),
+            Ok(x) => write!(f, " Puzzle {}: {}", self.part, x),
+            Err(e) => write!(f, " Puzzle {}: {}", self.part, e),
+        }?;
+        if self.cached {
+            write!(f, " (cached)")?;
+        }
+        match &self.verdict {
+            Some(verdict) => write!(f, " [{}]", verdict),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for DayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}: {}", self.day, self.title)?;
+        if let Some(e) = &self.error {
+            write!(f, "\n Input {}", e)?;
+        }
+        for part in self.parts.iter() {
+            write!(f, "\n{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Table comparing the answers of the same day on different inputs. Multi-line answers are
+/// printed below the table.
+pub fn variants_table(reports: &[DayReport]) -> String {
+    let cell = |report: &DayReport, part: u8| -> String {
+        let text = match (&report.error, report.part(part)) {
+            (Some(e), _) => e.to_string(),
+            (None, None) => String::new(),
+            (None, Some(p)) => match &p.answer {
+                Ok(a) if a.is_multiline() => String::from("(see below)"),
+                Ok(a) => a.to_string(),
+                Err(e) => e.to_string(),
+            },
+        };
+        let verdict = report
+            .part(part)
+            .and_then(|p| p.verdict.as_ref())
+            .map(|v| match v {
+                Verdict::Fail { expected } if expected.is_multiline() => String::from(" [FAIL]"),
+                v => format!(" [{}]", v),
+            })
+            .unwrap_or_default();
+        text.lines().next().unwrap_or_default().to_string() + &verdict
+    };
+
+    let width = reports
+        .iter()
+        .map(|r| r.input.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+    let mut table = vec![format!(
+        "{:<width$} | {:<24} | Puzzle 2",
+        "Input", "Puzzle 1"
+    )];
+    let mut grids = Vec::new();
+    for report in reports.iter() {
+        let row = format!(
+            "{:<width$} | {:<24} | {}",
+            report.input,
+            cell(report, 1),
+            cell(report, 2)
+        );
+        table.push(row.trim_end().to_string());
+        for part in report.parts.iter() {
+            if let Ok(answer) = &part.answer {
+                if answer.is_multiline() {
+                    grids.push(format!(
+                        "{} puzzle {}:\n{}",
+                        report.input, part.part, answer
+                    ));
+                }
+            }
+        }
+    }
+    table.extend(grids);
+    table.join("\n")
+}
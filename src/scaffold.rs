@@ -0,0 +1,390 @@
+use crate::readme::add_readme_row;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ScaffoldError {
+    #[error("Day must be between 1 and 25, got {}.", .0)]
+    InvalidDay(u32),
+    #[error("Day {} already exists in {}.", .0, .1)]
+    DayExists(u32, String),
+    #[error("Could not read {}: {}", .0, .1)]
+    Reading(String, std::io::Error),
+    #[error("Could not write {}: {}", .0, .1)]
+    Writing(String, std::io::Error),
+    #[error("Could not find where to add day {} in {}.", .0, .1)]
+    NoInsertionPoint(u32, String),
+}
+
+const TEMPLATE: &str = r#"use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
+    #[error("puzzle not solved yet")]
+    NotSolved,
+}
+
+pub struct Day__NN__;
+
+impl Solution for Day__NN__ {
+    const DAY: u32 = __DAY__;
+    const TITLE: &'static str = __TITLE__;
+
+    type Input = Vec<String>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
+
+    fn parse(input_data: &str) -> Result<Vec<String>, PuzzleError> {
+        Ok(lines(input_data)
+            .iter()
+            .map(|line| line.text.trim().to_string())
+            .collect())
+    }
+
+    fn part_1(_input: &Vec<String>) -> Result<usize, PuzzleError> {
+        Err(PuzzleError::NotSolved)
+    }
+
+    fn part_2(_input: &Vec<String>) -> Result<usize, PuzzleError> {
+        Err(PuzzleError::NotSolved)
+    }
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day__NN__::part_1(&Day__NN__::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
+    Day__NN__::part_2(&Day__NN__::parse(input_data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solutions::day__NN__::{puzzle_1, puzzle_2};
+
+    const EXAMPLE_1: &str = "";
+
+    #[test]
+    #[ignore = "example answer not filled in yet"]
+    fn puzzle_1_example() {
+        assert_eq!(puzzle_1(EXAMPLE_1), Ok(0));
+    }
+
+    #[test]
+    #[ignore = "example answer not filled in yet"]
+    fn puzzle_2_example() {
+        assert_eq!(puzzle_2(EXAMPLE_1), Ok(0));
+    }
+}
+"#;
+
+/// Source of a new day module, with the same layout as the solved days.
+pub fn day_module(day: u32, title: &str) -> String {
+    TEMPLATE
+        .replace("__NN__", &format!("{:02}", day))
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", &format!("{:?}", title))
+}
+
+/// Day number of a line if it starts (after indentation) with `prefix` and a number.
+fn day_of_line(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits = rest
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+/// Insert `new_lines` among the lines that belong to days, keeping them in day order. Each
+/// day's entry is found by `prefix` and starts `lead` lines above that line.
+pub(crate) fn insert_in_order(
+    text: &str,
+    day: u32,
+    prefix: &str,
+    lead: usize,
+    new_lines: &[String],
+) -> Option<String> {
+    let mut lines = text.lines().map(String::from).collect::<Vec<_>>();
+    let entries = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_of_line(line, prefix).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    let at = match entries.iter().find(|(_, d)| *d > day) {
+        Some((i, _)) => i.checked_sub(lead)?,
+        None => entries.last()?.0 + 1,
+    };
+    lines.splice(at..at, new_lines.iter().cloned());
+    Some(lines.join("\n") + "\n")
+}
+
+/// Declare the day's module and add it to the `register_days!` list in `solutions/mod.rs`.
+pub fn register_module(mod_rs: &str, day: u32) -> Option<String> {
+    let module = format!("pub mod day{:02};", day);
+    let with_module = insert_in_order(mod_rs, day, "pub mod day", 0, &[module])?;
+    let entry = format!("    day{:02}::Day{:02},", day, day);
+    insert_in_order(&with_module, day, "day", 0, &[entry])
+}
+
+/// Add the day's error to the imports and variants of `AocError` in `error.rs`.
+pub fn register_error(error_rs: &str, day: u32) -> Option<String> {
+    let variant = vec![
+        String::from("    #[error(transparent)]"),
+        format!("    Day{:02}(#[from] day{:02}::PuzzleError),", day, day),
+    ];
+    let with_variant = insert_in_order(error_rs, day, "Day", 1, &variant)?;
+
+    let start = with_variant.find("use crate::solutions::{")?;
+    let end = start + with_variant[start..].find("};")? + 2;
+    let mut modules = with_variant[start..end - 2]
+        .trim_start_matches("use crate::solutions::{")
+        .split(',')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    modules.push(format!("day{:02}", day));
+    modules.sort();
+    modules.dedup();
+    Some(format!(
+        "{}{}{}",
+        &with_variant[..start],
+        use_list("use crate::solutions::", &modules),
+        &with_variant[end..]
+    ))
+}
+
+/// Format a `use` list the way rustfmt does: on one line if it fits, otherwise wrapped.
+fn use_list(path: &str, items: &[String]) -> String {
+    let one_line = format!("{}{{{}}};", path, items.join(", "));
+    if one_line.len() <= 100 {
+        return one_line;
+    }
+    let mut lines = Vec::new();
+    let mut line = String::from("   ");
+    for item in items {
+        if line.len() + item.len() + 2 > 100 {
+            lines.push(line);
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {},", item));
+    }
+    lines.push(line);
+    format!("{}{{\n{}\n}};", path, lines.join("\n"))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|e| ScaffoldError::Reading(path.display().to_string(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|e| ScaffoldError::Writing(path.display().to_string(), e))
+}
+
+/// Create `src/solutions/dayNN.rs` from the template in the crate at `root`, register it
+/// in `solutions/mod.rs` and `AocError`, and add it to the README table. Nothing is
+/// written if any of the files cannot be updated. Returns the paths that were written.
+pub fn new_day(root: &Path, day: u32, title: &str) -> Result<Vec<String>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let module = root.join(format!("src/solutions/day{:02}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::DayExists(day, module.display().to_string()));
+    }
+
+    let mod_rs = root.join("src/solutions/mod.rs");
+    let error_rs = root.join("src/error.rs");
+    let readme = root.join("README.md");
+    let missing = |path: &Path| ScaffoldError::NoInsertionPoint(day, path.display().to_string());
+    let changes = [
+        (module.clone(), day_module(day, title)),
+        (
+            mod_rs.clone(),
+            register_module(&read(&mod_rs)?, day).ok_or_else(|| missing(&mod_rs))?,
+        ),
+        (
+            error_rs.clone(),
+            register_error(&read(&error_rs)?, day).ok_or_else(|| missing(&error_rs))?,
+        ),
+        (
+            readme.clone(),
+            add_readme_row(&read(&readme)?, day).ok_or_else(|| missing(&readme))?,
+        ),
+    ];
+    for (path, contents) in changes.iter() {
+        write(path, contents)?;
+    }
+    Ok(changes
+        .iter()
+        .map(|(path, _)| path.display().to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::{
+        day_module, day_of_line, new_day, register_error, register_module, ScaffoldError,
+    };
+    use crate::solutions::DAYS;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    const MOD_RS: &str = include_str!("solutions/mod.rs");
+    const ERROR_RS: &str = include_str!("error.rs");
+
+    #[test]
+    fn template() {
+        let module = day_module(13, "Distress \"Signal\"");
+        assert!(module.contains("pub struct Day13;"));
+        assert!(module.contains("const DAY: u32 = 13;"));
+        assert!(module.contains(r#"const TITLE: &'static str = "Distress \"Signal\"";"#));
+        assert!(module.contains("use crate::solutions::day13::{puzzle_1, puzzle_2};"));
+    }
+
+    #[test]
+    fn register_in_mod_rs() {
+        let updated = register_module(MOD_RS, 25).unwrap();
+        assert!(updated.contains("pub mod day25;\n"));
+        assert!(updated.contains("    day25::Day25,\n}"));
+
+        let mod_rs = "pub mod day01;
+pub mod day03;
+
+register_days! {
+    day01::Day01,
+    day03::Day03,
+}
+";
+        assert_eq!(
+            register_module(mod_rs, 2).unwrap(),
+            "pub mod day01;
+pub mod day02;
+pub mod day03;
+
+register_days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+}
+"
+        );
+    }
+
+    #[test]
+    fn register_in_error_rs() {
+        let updated = register_error(ERROR_RS, 25).unwrap();
+        assert!(updated.contains("    Day25(#[from] day25::PuzzleError),\n}"));
+        assert!(updated.contains(" day25,\n};"));
+
+        let error_rs = "use crate::solutions::{day01, day03};
+
+pub enum AocError {
+    #[error(transparent)]
+    Day01(#[from] day01::PuzzleError),
+    #[error(transparent)]
+    Day03(#[from] day03::PuzzleError),
+}
+";
+        assert_eq!(
+            register_error(error_rs, 2).unwrap(),
+            "use crate::solutions::{day01, day02, day03};
+
+pub enum AocError {
+    #[error(transparent)]
+    Day01(#[from] day01::PuzzleError),
+    #[error(transparent)]
+    Day02(#[from] day02::PuzzleError),
+    #[error(transparent)]
+    Day03(#[from] day03::PuzzleError),
+}
+"
+        );
+    }
+
+    #[test]
+    fn existing_or_invalid_day() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(matches!(
+            new_day(root, 1, "Calorie Counting"),
+            Err(ScaffoldError::DayExists(1, _))
+        ));
+        assert!(matches!(
+            new_day(root, 26, "Too late"),
+            Err(ScaffoldError::InvalidDay(26))
+        ));
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap().map(Result::unwrap) {
+            let target = to.join(entry.file_name());
+            match entry.file_type().unwrap().is_dir() {
+                true => copy_dir(&entry.path(), &target),
+                false => drop(fs::copy(entry.path(), target).unwrap()),
+            }
+        }
+    }
+
+    /// A copy of the sources and the README in a fresh temporary directory.
+    fn copy_of_tree() -> PathBuf {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let copy = std::env::temp_dir().join(format!("aoc-scaffold-{}", uuid::Uuid::new_v4()));
+        copy_dir(&root.join("src"), &copy.join("src"));
+        fs::copy(root.join("README.md"), copy.join("README.md")).unwrap();
+        copy
+    }
+
+    /// Days of the `(day, function)` entries of a static table.
+    fn table_days(source: &str, table: &str) -> Vec<u32> {
+        let start = source.find(table).unwrap();
+        let end = start + source[start..].find("];").unwrap();
+        source[start..end]
+            .lines()
+            .filter_map(|line| day_of_line(line, "("))
+            .collect()
+    }
+
+    #[test]
+    fn new_day_keeps_registries_consistent() {
+        let days = DAYS.iter().map(|p| p.day()).collect::<Vec<_>>();
+        let day = days.iter().max().unwrap() + 1;
+        let copy = copy_of_tree();
+        new_day(&copy, day, "Distress Signal").unwrap();
+        let read = |file: &str| fs::read_to_string(copy.join(file)).unwrap();
+
+        let mut registered = days.clone();
+        registered.push(day);
+        let in_file = |file: &str, prefix: &str| {
+            read(file)
+                .lines()
+                .filter_map(|line| day_of_line(line, prefix))
+                .collect::<Vec<_>>()
+        };
+        assert!(copy
+            .join(format!("src/solutions/day{:02}.rs", day))
+            .exists());
+        assert_eq!(in_file("src/solutions/mod.rs", "day"), registered);
+        assert_eq!(in_file("src/error.rs", "Day"), registered);
+        assert!(read("README.md").contains(&format!("src/solutions/day{:02}.rs", day)));
+
+        // The new day has no generator, reference or linter yet, and the tests and commands
+        // that use them skip days without one.
+        for (file, table) in [
+            ("src/inputgen.rs", "static GENERATORS"),
+            ("src/reference.rs", "static REFERENCES"),
+            ("src/lint.rs", "static LINTERS"),
+        ] {
+            let entries = table_days(&read(file), table);
+            assert!(entries.iter().all(|d| days.contains(d)), "{}", file);
+        }
+        fs::remove_dir_all(copy).unwrap();
+    }
+}
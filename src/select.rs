@@ -0,0 +1,133 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SelectionError {
+    #[error("Invalid day '{}'.", .0)]
+    InvalidDay(String),
+    #[error("Day range '{}' is empty.", .0)]
+    EmptyRange(String),
+    #[error("Day must be between 1 and 25, got {}.", .0)]
+    OutOfCalendar(u32),
+}
+
+/// Days picked on the command line: a single day (`7`), a range (`3..=7` or `3..8`) or a
+/// comma-separated list of either (`1,5,9` or `1,3..=5`). The days are sorted and unique.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(Vec<u32>);
+
+impl DaySelection {
+    pub fn days(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// The day if exactly one is selected.
+    pub fn single(&self) -> Option<u32> {
+        match self.0[..] {
+            [day] => Some(day),
+            _ => None,
+        }
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, SelectionError> {
+    day.trim()
+        .parse()
+        .map_err(|_| SelectionError::InvalidDay(day.trim().to_string()))
+}
+
+/// Check that a day is one of the 25 days of the calendar, so that a range never has more
+/// than 25 days in it.
+fn in_calendar(day: u32) -> Result<u32, SelectionError> {
+    match (1..=25).contains(&day) {
+        true => Ok(day),
+        false => Err(SelectionError::OutOfCalendar(day)),
+    }
+}
+
+fn parse_range(range: &str) -> Result<Vec<u32>, SelectionError> {
+    let days = if let Some((start, end)) = range.split_once("..=") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        (in_calendar(start)?..=in_calendar(end)?).collect::<Vec<_>>()
+    } else if let Some((start, end)) = range.split_once("..") {
+        let (start, end) = (parse_day(start)?, parse_day(end)?);
+        if end > start {
+            in_calendar(end - 1)?;
+        }
+        (in_calendar(start)?..end).collect::<Vec<_>>()
+    } else {
+        vec![in_calendar(parse_day(range)?)?]
+    };
+    match days.is_empty() {
+        true => Err(SelectionError::EmptyRange(range.trim().to_string())),
+        false => Ok(days),
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = SelectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+        for range in s.split(',') {
+            days.extend(parse_range(range)?);
+        }
+        days.sort();
+        days.dedup();
+        Ok(DaySelection(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select::{DaySelection, SelectionError};
+
+    fn days(s: &str) -> Vec<u32> {
+        s.parse::<DaySelection>().unwrap().days().to_vec()
+    }
+
+    #[test]
+    fn parse_selections() {
+        assert_eq!(days("7"), vec![7]);
+        assert_eq!(days("3..=7"), vec![3, 4, 5, 6, 7]);
+        assert_eq!(days("3..6"), vec![3, 4, 5]);
+        assert_eq!(days("9,1,5"), vec![1, 5, 9]);
+        assert_eq!(days("1, 3..=4, 4"), vec![1, 3, 4]);
+        assert_eq!("7".parse::<DaySelection>().unwrap().single(), Some(7));
+        assert_eq!("1,2".parse::<DaySelection>().unwrap().single(), None);
+    }
+
+    #[test]
+    fn invalid_selections() {
+        assert_eq!(
+            "1,x".parse::<DaySelection>(),
+            Err(SelectionError::InvalidDay("x".to_string()))
+        );
+        assert_eq!(
+            "7..=3".parse::<DaySelection>(),
+            Err(SelectionError::EmptyRange("7..=3".to_string()))
+        );
+        assert!("".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn days_outside_the_calendar() {
+        assert_eq!(days("1..26"), (1..=25).collect::<Vec<_>>());
+        assert_eq!(
+            "1..=4000000000".parse::<DaySelection>(),
+            Err(SelectionError::OutOfCalendar(4000000000))
+        );
+        assert_eq!(
+            "20..4000000000".parse::<DaySelection>(),
+            Err(SelectionError::OutOfCalendar(3999999999))
+        );
+        assert_eq!(
+            "0,3".parse::<DaySelection>(),
+            Err(SelectionError::OutOfCalendar(0))
+        );
+        assert_eq!(
+            "26".parse::<DaySelection>(),
+            Err(SelectionError::OutOfCalendar(26))
+        );
+    }
+}
@@ -1,64 +1,73 @@
-use std::string::ParseError;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::blocks;
+use crate::puzzle::Solution;
+use thiserror::Error;
 
-use crate::data::load;
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
+    #[error("No elves in input.")]
+    NoElves,
+    #[error("Calories of the top three elves do not fit in 32 bits.")]
+    TopThreeOverflow,
+}
 
-fn parse_puzzle_input(input_data: &str) -> Result<Vec<u32>, ParseError> {
+fn parse_puzzle_input(input_data: &str) -> Result<Vec<u32>, PuzzleError> {
     let mut elves: Vec<u32> = vec![];
-    let mut new_elf: u32 = 0;
-    for item in input_data.lines() {
-        if item.trim().is_empty() {
-            elves.push(new_elf);
-            new_elf = 0;
-        } else {
-            let item_value: u32 = item.trim().parse().unwrap();
-            new_elf += item_value
+    for block in blocks(input_data) {
+        let mut new_elf: u32 = 0;
+        for item in block {
+            new_elf = new_elf
+                .checked_add(item.parse()?)
+                .ok_or_else(|| item.error("calories of the elf do not fit in 32 bits"))?;
         }
-    }
-    if new_elf > 0 {
         elves.push(new_elf);
     }
     Ok(elves)
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<u32, ParseError> {
-    Ok(*parse_puzzle_input(input_data)
-        .unwrap()
-        .iter()
-        .max()
-        .unwrap())
-}
+pub struct Day01;
 
-pub fn puzzle_2(input_data: &str) -> Result<u32, ParseError> {
-    let mut elf_cals = parse_puzzle_input(input_data).unwrap();
-    elf_cals.sort_by(|a, b| b.cmp(a));
-    elf_cals.truncate(3);
-    Ok(elf_cals.iter().sum())
-}
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Calorie Counting";
 
-pub fn main(data_dir: &str) {
-    println!("Day 1: Calorie Counting");
-    let data = load(data_dir, 1, None);
+    type Input = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = PuzzleError;
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        _ => panic!("No solution to puzzle 1."),
+    fn parse(input_data: &str) -> Result<Vec<u32>, PuzzleError> {
+        parse_puzzle_input(input_data)
     }
-    assert_eq!(answer_1, Ok(68787));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        _ => panic!("No solution to puzzle 2."),
+    fn part_1(elf_cals: &Vec<u32>) -> Result<u32, PuzzleError> {
+        elf_cals.iter().max().cloned().ok_or(PuzzleError::NoElves)
+    }
+
+    fn part_2(elf_cals: &Vec<u32>) -> Result<u32, PuzzleError> {
+        let mut elf_cals = elf_cals.clone();
+        elf_cals.sort_by(|a, b| b.cmp(a));
+        elf_cals.truncate(3);
+        elf_cals
+            .iter()
+            .try_fold(0u32, |total, cals| total.checked_add(*cals))
+            .ok_or(PuzzleError::TopThreeOverflow)
     }
-    assert_eq!(answer_2, Ok(198041))
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<u32, PuzzleError> {
+    Day01::part_1(&Day01::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<u32, PuzzleError> {
+    Day01::part_2(&Day01::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day01::{puzzle_1, puzzle_2};
+    use crate::solutions::day01::{puzzle_1, puzzle_2, PuzzleError};
 
     const EXAMPLE_INPUT: &str = "
         1000
@@ -85,4 +94,12 @@ mod tests {
     fn example_1_puzzle_2() {
         assert_eq!(puzzle_2(self::EXAMPLE_INPUT), Ok(45000));
     }
+
+    #[test]
+    fn top_three_overflow() {
+        assert_eq!(
+            puzzle_2("4294967295\n\n1"),
+            Err(PuzzleError::TopThreeOverflow)
+        );
+    }
 }
@@ -1,14 +1,16 @@
-use crate::data::load;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Day2Error {
-    #[error("input data parsing")]
-    Parsing(String),
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
-enum Shape {
+pub enum Shape {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
@@ -28,103 +30,114 @@ struct GameRound {
 
 impl GameRound {
     fn result(self) -> GameResult {
-        match (self.you as i8 - self.opponent as i8) % 3i8 {
+        match (self.you as i8 - self.opponent as i8).rem_euclid(3) {
             0 => GameResult::Draw,
-            1 | -2 => GameResult::Win,
-            2 | -1 => GameResult::Lose,
-            x => panic!(
-                "Unexpected game calculation result: {:?} {:?} = {}",
-                self.you, self.opponent, x
-            ),
+            1 => GameResult::Win,
+            _ => GameResult::Lose,
         }
     }
 }
 
-// fn parse_puzzle_input(input_data: &str) -> () {}
-
-pub fn puzzle_1(input_data: &str) -> Result<u32, Day2Error> {
-    let mut tally: u32 = 0;
-    for line in input_data.trim().lines() {
-        let plays: Vec<&str> = line.trim().split(' ').collect();
-        assert_eq!(plays.len(), 2, "plays: {:?}", plays);
+/// The second column of the strategy guide. Puzzle 1 reads it as the shape to play and
+/// puzzle 2 reads it as the required result of the round.
+#[derive(Clone, Copy, Debug)]
+pub enum Strategy {
+    X,
+    Y,
+    Z,
+}
 
-        let opponent = match plays[0] {
+fn parse_puzzle_input(input_data: &str) -> Result<Vec<(Shape, Strategy)>, Day2Error> {
+    let mut rounds = Vec::new();
+    for line in lines(input_data) {
+        let [opponent, strategy] = line.fields("{} {}")?;
+        let opponent = match opponent.text {
             "A" => Ok(Shape::Rock),
             "B" => Ok(Shape::Paper),
             "C" => Ok(Shape::Scissors),
-            v => Err(Day2Error::Parsing(v.to_string())),
+            _ => Err(opponent.error("expected `A`, `B` or `C`")),
         }?;
-        let you = match plays[1] {
-            "X" => Ok(Shape::Rock),
-            "Y" => Ok(Shape::Paper),
-            "Z" => Ok(Shape::Scissors),
-            v => Err(Day2Error::Parsing(v.to_string())),
+        let strategy = match strategy.text {
+            "X" => Ok(Strategy::X),
+            "Y" => Ok(Strategy::Y),
+            "Z" => Ok(Strategy::Z),
+            _ => Err(strategy.error("expected `X`, `Y` or `Z`")),
         }?;
-        let game = GameRound { opponent, you };
-        tally += game.you as u32 + game.result() as u32;
+        rounds.push((opponent, strategy));
     }
-    Ok(tally)
+    Ok(rounds)
 }
 
-pub fn puzzle_2(input_data: &str) -> Result<u32, Day2Error> {
-    let mut tally: u32 = 0;
-    for line in input_data.trim().lines() {
-        let plays: Vec<&str> = line.trim().split(' ').collect();
-        assert_eq!(plays.len(), 2, "plays: {:?}", plays);
+pub struct Day02;
 
-        let opponent = match plays[0] {
-            "A" => Ok(Shape::Rock),
-            "B" => Ok(Shape::Paper),
-            "C" => Ok(Shape::Scissors),
-            v => Err(Day2Error::Parsing(v.to_string())),
-        }?;
-        let result = match plays[1] {
-            "X" => Ok(GameResult::Lose),
-            "Y" => Ok(GameResult::Draw),
-            "Z" => Ok(GameResult::Win),
-            v => Err(Day2Error::Parsing(v.to_string())),
-        }?;
-        let you: u32 = match result {
-            GameResult::Lose => {
-                if opponent == Shape::Rock {
-                    3
-                } else {
-                    opponent as u32 - 1
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input = Vec<(Shape, Strategy)>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Day2Error;
+
+    fn parse(input_data: &str) -> Result<Self::Input, Day2Error> {
+        parse_puzzle_input(input_data)
+    }
+
+    fn part_1(rounds: &Self::Input) -> Result<u32, Day2Error> {
+        let mut tally: u32 = 0;
+        for (opponent, strategy) in rounds.iter() {
+            let you = match strategy {
+                Strategy::X => Shape::Rock,
+                Strategy::Y => Shape::Paper,
+                Strategy::Z => Shape::Scissors,
+            };
+            let game = GameRound {
+                opponent: *opponent,
+                you,
+            };
+            tally += game.you as u32 + game.result() as u32;
+        }
+        Ok(tally)
+    }
+
+    fn part_2(rounds: &Self::Input) -> Result<u32, Day2Error> {
+        let mut tally: u32 = 0;
+        for (opponent, strategy) in rounds.iter() {
+            let opponent = *opponent;
+            let result = match strategy {
+                Strategy::X => GameResult::Lose,
+                Strategy::Y => GameResult::Draw,
+                Strategy::Z => GameResult::Win,
+            };
+            let you: u32 = match result {
+                GameResult::Lose => {
+                    if opponent == Shape::Rock {
+                        3
+                    } else {
+                        opponent as u32 - 1
+                    }
                 }
-            }
-            GameResult::Draw => opponent as u32,
-            GameResult::Win => {
-                if opponent == Shape::Scissors {
-                    1
-                } else {
-                    opponent as u32 + 1
+                GameResult::Draw => opponent as u32,
+                GameResult::Win => {
+                    if opponent == Shape::Scissors {
+                        1
+                    } else {
+                        opponent as u32 + 1
+                    }
                 }
-            }
-        };
-        tally += you + result as u32;
+            };
+            tally += you + result as u32;
+        }
+        Ok(tally)
     }
-    Ok(tally)
 }
 
-pub fn main(data_dir: &str) {
-    println!("Day 2: Rock Paper Scissors");
-    let data = load(data_dir, 2, None);
-
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
-    }
-    assert_eq!(answer_1, Ok(11873));
+pub fn puzzle_1(input_data: &str) -> Result<u32, Day2Error> {
+    Day02::part_1(&Day02::parse(input_data)?)
+}
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
-    }
-    assert_eq!(answer_2, Ok(12014))
+pub fn puzzle_2(input_data: &str) -> Result<u32, Day2Error> {
+    Day02::part_2(&Day02::parse(input_data)?)
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use crate::data::load;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
@@ -6,6 +8,8 @@ use thiserror::Error;
 pub enum Day3Error {
     #[error("no shared itmes")]
     NoSharedItem,
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 struct AlphaScore {
@@ -51,63 +55,72 @@ impl RuckSack {
     }
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<u32, Day3Error> {
-    let score_map = AlphaScore::new();
-    let mut tally = 0;
-    for line in input_data.lines() {
-        if line.trim().is_empty() {
-            continue;
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Input = Vec<String>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = Day3Error;
+
+    fn parse(input_data: &str) -> Result<Vec<String>, Day3Error> {
+        let mut rucksacks = Vec::new();
+        for line in lines(input_data) {
+            let line = line.trim();
+            if let Some((item, _)) = line.chars().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(item
+                    .error("expected an item from `a` to `z` or `A` to `Z`")
+                    .into());
+            }
+            if line.text.len() % 2 == 1 {
+                let message = "expected an even number of items, half in each compartment";
+                return Err(line.error(message).into());
+            }
+            rucksacks.push(line.text.to_string());
         }
-        let rucksack = RuckSack::from_str(line.trim());
-        tally += score_map.score[&rucksack.first_shared_item().unwrap()];
+        Ok(rucksacks)
     }
-    Ok(tally)
-}
 
-pub fn puzzle_2(input_data: &str) -> Result<u32, Day3Error> {
-    let mut tally = 0;
-    let scorer = AlphaScore::new();
-    let iter: Vec<&str> = input_data
-        .lines()
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-        .collect();
-    for group in iter.windows(3).step_by(3) {
-        let mut counter = HashMap::new();
-        for line in group {
-            let set: HashSet<char> = line.chars().collect();
-            for c in set {
-                counter.entry(c).and_modify(|e| *e += 1).or_insert(1);
-            }
+    fn part_1(rucksacks: &Vec<String>) -> Result<u32, Day3Error> {
+        let score_map = AlphaScore::new();
+        let mut tally = 0;
+        for line in rucksacks.iter() {
+            let rucksack = RuckSack::from_str(line);
+            tally += score_map.score[&rucksack.first_shared_item()?];
         }
-        for (c, n) in counter.iter() {
-            if n == &3 {
-                tally += scorer.score[c];
+        Ok(tally)
+    }
+
+    fn part_2(rucksacks: &Vec<String>) -> Result<u32, Day3Error> {
+        let mut tally = 0;
+        let scorer = AlphaScore::new();
+        for group in rucksacks.windows(3).step_by(3) {
+            let mut counter = HashMap::new();
+            for line in group {
+                let set: HashSet<char> = line.chars().collect();
+                for c in set {
+                    counter.entry(c).and_modify(|e| *e += 1).or_insert(1);
+                }
+            }
+            for (c, n) in counter.iter() {
+                if n == &3 {
+                    tally += scorer.score[c];
+                }
             }
         }
+        Ok(tally)
     }
-    Ok(tally)
 }
 
-pub fn main(data_dir: &str) {
-    println!("Day 3: Rucksack Reorganization");
-    let data = load(data_dir, 3, None);
-
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
-    }
-    assert_eq!(answer_1, Ok(7446));
+pub fn puzzle_1(input_data: &str) -> Result<u32, Day3Error> {
+    Day03::part_1(&Day03::parse(input_data)?)
+}
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
-    }
-    assert_eq!(answer_2, Ok(2646))
+pub fn puzzle_2(input_data: &str) -> Result<u32, Day3Error> {
+    Day03::part_2(&Day03::parse(input_data)?)
 }
 
 #[cfg(test)]
@@ -1,129 +1,97 @@
-use crate::data::load;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::{lines, Span};
+use crate::puzzle::Solution;
 use std::ops::RangeInclusive;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleError {
-    #[error("could not split pair data")]
-    SplittingPair(String),
-    #[error("could not split elf range")]
-    SplittingElfRange,
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 #[derive(Debug, Clone, Copy)]
-struct ElfRange {
+pub struct ElfRange {
     from: u32,
     to: u32,
 }
 
 impl ElfRange {
-    fn from_str(pair_str: &str) -> Self {
-        let mut split_data = pair_str.split('-').collect::<Vec<&str>>();
-        assert_eq!(split_data.len(), 2);
-        ElfRange {
-            to: split_data
-                .pop()
-                .ok_or(PuzzleError::SplittingElfRange)
-                .unwrap()
-                .parse::<u32>()
-                .unwrap(),
-            from: split_data
-                .pop()
-                .ok_or(PuzzleError::SplittingElfRange)
-                .unwrap()
-                .parse::<u32>()
-                .unwrap(),
-        }
-    }
-
     fn to_range(self) -> RangeInclusive<u32> {
         RangeInclusive::new(self.from, self.to)
     }
 
+    /// Compares the ends of the ranges rather than every section, so that wide ranges take
+    /// no longer. A range that ends before it starts has no sections.
     fn contains(self, elf: &ElfRange) -> bool {
-        let r = self.to_range();
-        (elf.from..=elf.to).map(|x| r.contains(&x)).all(|x| x)
+        elf.to_range().is_empty() || (self.from <= elf.from && elf.to <= self.to)
     }
 
     fn overlaps(self, elf: &ElfRange) -> bool {
-        let r = self.to_range();
-        (elf.from..=elf.to).map(|x| r.contains(&x)).any(|x| x)
+        self.from.max(elf.from) <= self.to.min(elf.to)
     }
 }
 
-fn parse_data(line: &str) -> Result<(ElfRange, ElfRange), PuzzleError> {
-    let mut pairs: Vec<&str> = line.split(',').collect();
-    match pairs.len() {
-        2 => (),
-        _ => return Err(PuzzleError::SplittingPair(line.to_string())),
+fn parse_data(line: Span) -> Result<(ElfRange, ElfRange), PuzzleError> {
+    let [from1, to1, from2, to2] = line.fields("{}-{},{}-{}")?;
+    let elf1 = ElfRange {
+        from: from1.parse()?,
+        to: to1.parse()?,
+    };
+    let elf2 = ElfRange {
+        from: from2.parse()?,
+        to: to2.parse()?,
     };
-    let elf2 = ElfRange::from_str(
-        pairs
-            .pop()
-            .ok_or(PuzzleError::SplittingPair(line.to_string()))?,
-    );
-    let elf1 = ElfRange::from_str(
-        pairs
-            .pop()
-            .ok_or(PuzzleError::SplittingPair(line.to_string()))?,
-    );
     Ok((elf1, elf2))
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<u32, PuzzleError> {
-    let mut count = 0;
-    for line in input_data
-        .lines()
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-    {
-        let (elf1, elf2) = parse_data(line)?;
-        if elf1.contains(&elf2) | elf2.contains(&elf1) {
-            count += 1;
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Input = Vec<(ElfRange, ElfRange)>;
+    type Output1 = u32;
+    type Output2 = u32;
+    type Error = PuzzleError;
+
+    fn parse(input_data: &str) -> Result<Self::Input, PuzzleError> {
+        lines(input_data).into_iter().map(parse_data).collect()
+    }
+
+    fn part_1(pairs: &Self::Input) -> Result<u32, PuzzleError> {
+        let mut count = 0;
+        for (elf1, elf2) in pairs.iter() {
+            if elf1.contains(elf2) | elf2.contains(elf1) {
+                count += 1;
+            }
         }
+        Ok(count)
     }
-    Ok(count)
-}
 
-pub fn puzzle_2(input_data: &str) -> Result<u32, PuzzleError> {
-    let mut count = 0;
-    for line in input_data
-        .lines()
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-    {
-        let (elf1, elf2) = parse_data(line)?;
-        if elf1.overlaps(&elf2) {
-            count += 1;
+    fn part_2(pairs: &Self::Input) -> Result<u32, PuzzleError> {
+        let mut count = 0;
+        for (elf1, elf2) in pairs.iter() {
+            if elf1.overlaps(elf2) {
+                count += 1;
+            }
         }
+        Ok(count)
     }
-    Ok(count)
 }
 
-pub fn main(data_dir: &str) {
-    println!("Day 4: Camp Cleanup");
-    let data = load(data_dir, 4, None);
-
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
-    }
-    assert_eq!(answer_1, Ok(507));
+pub fn puzzle_1(input_data: &str) -> Result<u32, PuzzleError> {
+    Day04::part_1(&Day04::parse(input_data)?)
+}
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
-    }
-    assert_eq!(answer_2, Ok(897))
+pub fn puzzle_2(input_data: &str) -> Result<u32, PuzzleError> {
+    Day04::part_2(&Day04::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day04::{puzzle_1, puzzle_2};
+    use crate::solutions::day04::{puzzle_1, puzzle_2, ElfRange};
 
     const EXAMPLE_1: &str = "
     2-4,6-8
@@ -142,4 +110,33 @@ mod tests {
     fn example_1_puzzle_2() {
         assert_eq!(puzzle_2(EXAMPLE_1), Ok(4))
     }
+
+    #[test]
+    fn compare_ends_of_ranges() {
+        let elf = |from, to| ElfRange { from, to };
+        assert!(elf(0, u32::MAX).contains(&elf(31, 4294967295)));
+        assert!(!elf(31, 4294967295).contains(&elf(0, 5)));
+        assert!(elf(31, 4294967295).overlaps(&elf(0, 31)));
+        assert!(!elf(31, 4294967295).overlaps(&elf(0, 30)));
+        // A range that ends before it starts is empty.
+        assert!(elf(3, 4).contains(&elf(9, 1)));
+        assert!(!elf(9, 1).contains(&elf(3, 4)));
+        assert!(!elf(1, 9).overlaps(&elf(5, 4)));
+    }
+
+    #[test]
+    fn malformed_section() {
+        assert_eq!(
+            puzzle_1("2-4,6-x\n").unwrap_err().to_string(),
+            "line 1, column 7: could not parse `x` as u32
+ 1 | 2-4,6-x
+   |       ^"
+        );
+        assert_eq!(
+            puzzle_1("2-4,6\n").unwrap_err().to_string(),
+            "line 1, column 6: expected `-`
+ 1 | 2-4,6
+   |      ^"
+        );
+    }
 }
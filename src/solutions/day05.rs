@@ -1,22 +1,25 @@
-use crate::data::load_raw;
-use textwrap::dedent;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::{end, lines, Span};
+use crate::puzzle::Solution;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleError {
     #[error("could not move crate from top of stack")]
     FailedTakeFromStack,
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 #[derive(Debug, Clone, Copy)]
-struct CraneOp {
+pub struct CraneOp {
     n: usize,
     from: usize,
     to: usize,
 }
 
 #[derive(Debug, Clone)]
-struct Supplies {
+pub struct Supplies {
     stacks: Vec<Vec<char>>,
 }
 
@@ -86,85 +89,91 @@ impl Supplies {
     }
 }
 
-fn parse_input(data: &str) -> (Supplies, Vec<CraneOp>) {
-    let data = dedent(data);
-    let mut final_line = 0;
+/// Parse a line like `move 1 from 2 to 1`, checking that both stacks exist.
+fn parse_crane_op(line: Span, n_stacks: usize) -> Result<CraneOp, PuzzleError> {
+    let [n, from, to] = line.fields("move {} from {} to {}")?;
+    let stack = |x: Span| match x.parse::<usize>()? {
+        i if (1..=n_stacks).contains(&i) => Ok(i),
+        i => Err(x.error(format!("there is no stack {}, only 1 to {}", i, n_stacks))),
+    };
+    Ok(CraneOp {
+        n: n.parse()?,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
+
+fn parse_input(data: &str) -> Result<(Supplies, Vec<CraneOp>), PuzzleError> {
+    let lines = lines(data);
+    let final_line = lines
+        .iter()
+        .position(|line| line.text.trim().starts_with('1'))
+        .ok_or_else(|| end(data).error("expected the line numbering the stacks"))?;
 
     // Parsing stacks.
     let mut supplies = Supplies::new();
-    for (i, line) in data.lines().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        if line.trim().starts_with('1') {
-            final_line = i;
-            break;
-        }
-        for (i, c) in line.chars().skip(1).step_by(4).enumerate() {
-            supplies = supplies.clone();
+    for line in lines[..final_line].iter() {
+        for (i, (span, c)) in line.chars().skip(1).step_by(4).enumerate() {
+            if (c != ' ') & !c.is_ascii_uppercase() {
+                return Err(span.error("expected a crate from `A` to `Z`").into());
+            }
             supplies.insert_at_bottom(i, c);
         }
     }
     supplies.drop_empty_crates();
 
     // Parsing crane instructions.
-    let mut crane_operations: Vec<CraneOp> = vec![];
-    for line in data.lines().skip(final_line + 1) {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let split_insts: Vec<_> = line.trim().splitn(6, ' ').collect();
-        let crane_op = CraneOp {
-            n: split_insts[1].parse().unwrap(),
-            from: split_insts[3].parse().unwrap(),
-            to: split_insts[5].parse().unwrap(),
-        };
-        crane_operations.push(crane_op)
-    }
+    let crane_operations = lines[final_line + 1..]
+        .iter()
+        .map(|line| parse_crane_op(*line, supplies.stacks.len()))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (supplies, crane_operations)
+    Ok((supplies, crane_operations))
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<String, PuzzleError> {
-    let (mut supplies, crane_ops) = parse_input(input_data);
-    for crane_op in crane_ops {
-        let _ = supplies.perform_9000(&crane_op);
-    }
-    supplies.top_of_stacks()
-}
+pub struct Day05;
 
-pub fn puzzle_2(input_data: &str) -> Result<String, PuzzleError> {
-    let (mut supplies, crane_ops) = parse_input(input_data);
-    for crane_op in crane_ops {
-        let _ = supplies.perform_9001(&crane_op);
-    }
-    supplies.top_of_stacks()
-}
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
 
-pub fn main(data_dir: &str) {
-    println!("Day 5: Supply Stacks");
-    let data = load_raw(data_dir, 5, None);
+    type Input = (Supplies, Vec<CraneOp>);
+    type Output1 = String;
+    type Output2 = String;
+    type Error = PuzzleError;
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn parse(input_data: &str) -> Result<Self::Input, PuzzleError> {
+        parse_input(input_data)
     }
-    assert_eq!(answer_1, Ok("RFFFWBPNS".to_string()));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn part_1((supplies, crane_ops): &Self::Input) -> Result<String, PuzzleError> {
+        let mut supplies = supplies.clone();
+        for crane_op in crane_ops {
+            supplies.perform_9000(crane_op)?;
+        }
+        supplies.top_of_stacks()
     }
-    assert_eq!(answer_2, Ok("CQQBBJFCS".to_string()));
+
+    fn part_2((supplies, crane_ops): &Self::Input) -> Result<String, PuzzleError> {
+        let mut supplies = supplies.clone();
+        for crane_op in crane_ops {
+            supplies.perform_9001(crane_op)?;
+        }
+        supplies.top_of_stacks()
+    }
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<String, PuzzleError> {
+    Day05::part_1(&Day05::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<String, PuzzleError> {
+    Day05::part_2(&Day05::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day05::{puzzle_1, puzzle_2};
+    use crate::solutions::day05::{puzzle_1, puzzle_2, PuzzleError};
 
     const EXAMPLE_1: &str = "
         [D]
@@ -187,4 +196,22 @@ mod tests {
     fn example_1_puzzle_2() {
         assert_eq!(puzzle_2(EXAMPLE_1), Ok("MCD".to_string()))
     }
+
+    #[test]
+    fn move_to_missing_stack() {
+        let input = EXAMPLE_1.replace("move 3 from 1 to 3", "move 3 from 1 to 4");
+        assert_eq!(
+            puzzle_1(&input).unwrap_err().to_string(),
+            "line 8, column 22: there is no stack 4, only 1 to 3
+ 8 |     move 3 from 1 to 4
+   |                      ^"
+        );
+    }
+
+    #[test]
+    fn move_more_crates_than_the_stack_holds() {
+        let input = EXAMPLE_1.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        assert_eq!(puzzle_1(&input), Err(PuzzleError::FailedTakeFromStack));
+        assert_eq!(puzzle_2(&input), Err(PuzzleError::FailedTakeFromStack));
+    }
 }
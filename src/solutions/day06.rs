@@ -1,4 +1,6 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use std::collections::HashSet;
 use thiserror::Error;
 
@@ -6,6 +8,8 @@ use thiserror::Error;
 pub enum PuzzleError {
     #[error("no window with all unique characters found")]
     NoUniqueWindowFound,
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 fn find_unique_window(data_stream: &str, window_size: usize) -> Result<usize, PuzzleError> {
@@ -23,33 +27,45 @@ fn find_unique_window(data_stream: &str, window_size: usize) -> Result<usize, Pu
     Err(PuzzleError::NoUniqueWindowFound)
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
-    find_unique_window(input_data, 4)
-}
+pub struct Day06;
 
-pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
-    find_unique_window(input_data, 14)
-}
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
 
-pub fn main(data_dir: &str) {
-    println!("Day 6: Tuning Trouble");
-    let data = load_raw(data_dir, 6, None);
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
+
+    fn parse(input_data: &str) -> Result<String, PuzzleError> {
+        let lines = lines(input_data);
+        if let Some(second) = lines.get(1) {
+            return Err(second
+                .error("expected the datastream on a single line")
+                .into());
+        }
+        Ok(lines
+            .first()
+            .map_or("", |line| line.text.trim())
+            .to_string())
+    }
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn part_1(data_stream: &String) -> Result<usize, PuzzleError> {
+        find_unique_window(data_stream, 4)
     }
-    assert_eq!(answer_1, Ok(1210));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
+    fn part_2(data_stream: &String) -> Result<usize, PuzzleError> {
+        find_unique_window(data_stream, 14)
     }
-    assert_eq!(answer_2, Ok(3476));
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day06::part_1(&Day06::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
+    Day06::part_2(&Day06::parse(input_data)?)
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 use uuid::Uuid;
@@ -9,12 +11,19 @@ pub enum PuzzleError {
     NoParentNode(String),
     #[error("No child.")]
     NoChildNode(String),
-    #[error("Failed to parse file size.")]
-    ParsingFileSize(String),
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
     #[error("No minimum size that meets constrains.")]
     NoMinimumValue,
+    #[error("{} used does not fit on the device of {}.", .0, DEVICE_SIZE)]
+    DiskOverfull(usize),
+    #[error("{} is already free, nothing needs to be deleted.", .0)]
+    EnoughFreeSpace(usize),
 }
 
+const DEVICE_SIZE: usize = 70000000;
+const SPACE_REQUIRED: usize = 30000000;
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Node {
     uuid: Uuid,
@@ -32,7 +41,7 @@ impl Node {
     }
 }
 
-struct Tree {
+pub struct Tree {
     root: Node,
     nodes: HashMap<Uuid, Node>,
     edges: HashMap<Node, HashSet<Node>>,
@@ -112,13 +121,13 @@ impl Tree {
         if let Some(s) = node_sizes.get(node) {
             return *s;
         };
-        let mut size = node.size;
-        size += match self.edges.get(node) {
+        // Saturate instead of overflowing: anything that large does not fit on the device.
+        let size = match self.edges.get(node) {
             Some(children) => children
                 .iter()
                 .map(|n| self.calculate_size(n, node_sizes))
-                .sum(),
-            None => 0,
+                .fold(node.size, usize::saturating_add),
+            None => node.size,
         };
         node_sizes.insert(node.clone(), size);
         size
@@ -139,92 +148,98 @@ impl Tree {
 fn build_filesystem_tree(input_data: &str) -> Result<Tree, PuzzleError> {
     let mut fs: Tree = Tree::new();
     let mut cwd = fs.root.clone();
-    for line in input_data.trim().lines().skip(1).map(|x| x.trim()) {
-        if line.is_empty() {
-            continue;
-        }
-        if line.starts_with("$ cd") {
-            let node_name = line.split(' ').collect::<Vec<_>>()[2];
-            if node_name == ".." {
-                cwd = fs.get_parent(&cwd)?;
-            } else {
-                cwd = fs.get_child(&cwd, node_name)?;
-            }
-        } else if line.starts_with("$ ls") {
-            continue;
-        } else if line.starts_with("dir") {
-            let dir_name = line.split(' ').collect::<Vec<_>>()[1];
-            fs.add_child(&cwd, dir_name, 0)?;
+    let mut lines = lines(input_data).into_iter();
+    if let Some(first) = lines.next() {
+        let [] = first.fields("$ cd /")?;
+    }
+    for line in lines {
+        let line = line.trim();
+        if line.text.starts_with("$ cd") {
+            let [node_name] = line.fields("$ cd {}")?;
+            cwd = match node_name.text {
+                "/" => fs.root.clone(),
+                ".." => fs
+                    .get_parent(&cwd)
+                    .map_err(|_| node_name.error("already in the outermost directory"))?,
+                name => fs
+                    .get_child(&cwd, name)
+                    .ok()
+                    .filter(|child| child.size == 0)
+                    .ok_or_else(|| node_name.error(format!("no directory `{}` listed", name)))?,
+            };
+        } else if line.text.starts_with("$ ls") {
+            let [] = line.fields("$ ls")?;
+        } else if line.text.starts_with("dir") {
+            let [dir_name] = line.fields("dir {}")?;
+            fs.add_child(&cwd, dir_name.text, 0)?;
         } else {
             // Is a file.
-            let split_line = line.split(' ').collect::<Vec<_>>();
-            let file_size: usize = match split_line[0].parse() {
-                Ok(x) => Ok(x),
-                Err(_) => Err(PuzzleError::ParsingFileSize(line.to_string())),
-            }?;
-            let file_name = line.split(' ').collect::<Vec<_>>()[1];
-            fs.add_child(&cwd, file_name, file_size)?;
+            let [file_size, file_name] = line.fields("{} {}")?;
+            fs.add_child(&cwd, file_name.text, file_size.parse()?)?;
         }
     }
     Ok(fs)
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
-    let fs = build_filesystem_tree(input_data)?;
-    let dir_nodes = fs.directory_nodes();
-    let size = fs
-        .calculate_sizes()
-        .iter()
-        .filter(|(n, s)| dir_nodes.contains(n) & (s <= &&100000))
-        .map(|(_, s)| s)
-        .sum();
-    Ok(size)
-}
-
-pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
-    let fs = build_filesystem_tree(input_data)?;
-    let dir_nodes = fs.directory_nodes();
-    let sizes = fs.calculate_sizes();
+pub struct Day07;
 
-    let device_size = 70000000;
-    let space_required = 30000000;
-    let space_used = sizes.get(&fs.root).unwrap();
-    let min_deletion_size = space_required - (device_size - space_used);
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
 
-    let deletion_size = sizes
-        .iter()
-        .filter(|(n, s)| dir_nodes.contains(n) & (s >= &&min_deletion_size))
-        .map(|(_, s)| s)
-        .min()
-        .ok_or(PuzzleError::NoMinimumValue)
-        .unwrap();
-    Ok(*deletion_size)
-}
+    type Input = Tree;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
 
-pub fn main(data_dir: &str) {
-    println!("Day 7: No Space Left On Device");
-    let data = load_raw(data_dir, 7, None);
+    fn parse(input_data: &str) -> Result<Tree, PuzzleError> {
+        build_filesystem_tree(input_data)
+    }
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn part_1(fs: &Tree) -> Result<usize, PuzzleError> {
+        let dir_nodes = fs.directory_nodes();
+        let size = fs
+            .calculate_sizes()
+            .iter()
+            .filter(|(n, s)| dir_nodes.contains(n) & (s <= &&100000))
+            .map(|(_, s)| s)
+            .sum();
+        Ok(size)
     }
-    assert_eq!(answer_1, Ok(1334506));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
+    fn part_2(fs: &Tree) -> Result<usize, PuzzleError> {
+        let dir_nodes = fs.directory_nodes();
+        let sizes = fs.calculate_sizes();
+
+        let space_used = sizes.get(&fs.root).copied().unwrap_or_default();
+        let space_free = DEVICE_SIZE
+            .checked_sub(space_used)
+            .ok_or(PuzzleError::DiskOverfull(space_used))?;
+        let min_deletion_size = SPACE_REQUIRED
+            .checked_sub(space_free)
+            .ok_or(PuzzleError::EnoughFreeSpace(space_free))?;
+
+        let deletion_size = sizes
+            .iter()
+            .filter(|(n, s)| dir_nodes.contains(n) & (s >= &&min_deletion_size))
+            .map(|(_, s)| s)
+            .min()
+            .ok_or(PuzzleError::NoMinimumValue)?;
+        Ok(*deletion_size)
     }
-    assert_eq!(answer_2, Ok(7421137));
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day07::part_1(&Day07::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
+    Day07::part_2(&Day07::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day07::{puzzle_1, puzzle_2};
+    use crate::solutions::day07::{puzzle_1, puzzle_2, PuzzleError};
 
     const EXAMPLE_1: &str = "
     $ cd /
@@ -260,4 +275,24 @@ mod tests {
     fn puzzle_2_examples() {
         assert_eq!(puzzle_2(EXAMPLE_1), Ok(24933642));
     }
+
+    #[test]
+    fn cd_into_unlisted_directory() {
+        let input = EXAMPLE_1.replace("$ cd e", "$ cd x");
+        assert_eq!(
+            puzzle_1(&input).unwrap_err().to_string(),
+            "line 14, column 10: no directory `x` listed
+ 14 |     $ cd x
+    |          ^"
+        );
+    }
+
+    #[test]
+    fn too_little_used_to_need_deleting() {
+        assert_eq!(
+            puzzle_2("$ cd /\n$ ls\n584 i"),
+            Err(PuzzleError::EnoughFreeSpace(69999416))
+        );
+        assert_eq!(puzzle_2(""), Err(PuzzleError::EnoughFreeSpace(70000000)));
+    }
 }
@@ -1,22 +1,20 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use std::cmp::max;
 use std::{collections::HashSet, fmt};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleError {
-    #[error("Could not parse the following direction: {}.", .0)]
-    UnknownDirection(String),
-    #[error("Failed parsing line: {}.", .0)]
-    FailedParsing(String),
-    #[error("Failed parsing integer: {}.", .0)]
-    ParseIntError(std::num::ParseIntError),
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
     #[error("Performing action on rope with no knots.")]
     NoKnots,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Up(usize),
     Down(usize),
     Left(usize),
@@ -175,33 +173,35 @@ impl Rope {
     }
 }
 
+/// Most steps the motions may take in total. The rope is moved one step at a time, so this
+/// keeps the run short; puzzle inputs take about ten thousand.
+pub const MAX_STEPS: usize = 100_000;
+
 fn parse_directions(input_data: &str) -> Result<Vec<Direction>, PuzzleError> {
     let mut directions = Vec::new();
-    for line in input_data.trim().lines().map(|x| x.trim()) {
-        let steps = match line.split(' ').collect::<Vec<_>>()[1]
-            .trim()
-            .to_string()
-            .parse::<usize>()
-        {
-            Ok(x) => Ok(x),
-            Err(e) => Err(PuzzleError::ParseIntError(e)),
-        }?;
-        let dir = match line.chars().next() {
-            Some('U') => Ok(Direction::Up(steps)),
-            Some('D') => Ok(Direction::Down(steps)),
-            Some('L') => Ok(Direction::Left(steps)),
-            Some('R') => Ok(Direction::Right(steps)),
-            Some(x) => Err(PuzzleError::UnknownDirection(x.to_string())),
-            None => Err(PuzzleError::FailedParsing(line.to_string())),
+    let mut total_steps: usize = 0;
+    for line in lines(input_data) {
+        let [dir, steps_span] = line.fields("{} {}")?;
+        let steps = steps_span.parse::<usize>()?;
+        total_steps = total_steps.saturating_add(steps);
+        if total_steps > MAX_STEPS {
+            let message = format!("the motions take more than {} steps", MAX_STEPS);
+            return Err(steps_span.error(message).into());
+        }
+        let dir = match dir.text {
+            "U" => Ok(Direction::Up(steps)),
+            "D" => Ok(Direction::Down(steps)),
+            "L" => Ok(Direction::Left(steps)),
+            "R" => Ok(Direction::Right(steps)),
+            _ => Err(dir.error("expected `U`, `D`, `L` or `R`")),
         }?;
         directions.push(dir)
     }
     Ok(directions)
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
-    let directions = parse_directions(input_data)?;
-    let mut rope = Rope::new(2);
+fn count_tail_locations(directions: &[Direction], n_knots: usize) -> Result<usize, PuzzleError> {
+    let mut rope = Rope::new(n_knots);
 
     let mut tail_locations = HashSet::new();
     for direction in directions.iter() {
@@ -211,42 +211,41 @@ pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
     Ok(tail_locations.len())
 }
 
-pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
-    let directions = parse_directions(input_data)?;
-    let mut rope = Rope::new(10);
+pub struct Day09;
 
-    let mut tail_locations = HashSet::new();
-    for direction in directions.iter() {
-        tail_locations.extend(rope.perform_motion(direction)?);
-    }
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    const TITLE: &'static str = "Rope Bridge";
 
-    Ok(tail_locations.len())
-}
+    type Input = Vec<Direction>;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
 
-pub fn main(data_dir: &str) {
-    println!("Day 9: Rope Bridge");
-    let data = load_raw(data_dir, 9, None);
+    fn parse(input_data: &str) -> Result<Vec<Direction>, PuzzleError> {
+        parse_directions(input_data)
+    }
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn part_1(directions: &Vec<Direction>) -> Result<usize, PuzzleError> {
+        count_tail_locations(directions, 2)
     }
-    assert_eq!(answer_1, Ok(6332));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
+    fn part_2(directions: &Vec<Direction>) -> Result<usize, PuzzleError> {
+        count_tail_locations(directions, 10)
     }
-    assert_eq!(answer_2, Ok(2511));
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day09::part_1(&Day09::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
+    Day09::part_2(&Day09::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day09::{puzzle_1, puzzle_2};
+    use crate::solutions::day09::{puzzle_1, puzzle_2, PuzzleError};
 
     const EXAMPLE_1: &str = "
     R 4
@@ -278,4 +277,20 @@ mod tests {
         assert_eq!(puzzle_2(EXAMPLE_1), Ok(1));
         assert_eq!(puzzle_2(EXAMPLE_2), Ok(36));
     }
+
+    #[test]
+    fn too_many_steps() {
+        let summary = |input: &str| match puzzle_1(input) {
+            Err(PuzzleError::Parsing(diagnostic)) => diagnostic.summary(),
+            other => panic!("expected a parsing error, got {:?}", other),
+        };
+        assert_eq!(
+            summary("R 4\nU 18446744073709551615"),
+            "line 2, column 3: the motions take more than 100000 steps"
+        );
+        assert_eq!(
+            summary(&"R 60000\n".repeat(2)),
+            "line 2, column 3: the motions take more than 100000 steps"
+        );
+    }
 }
@@ -1,12 +1,17 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::grid::{Grid, Position};
+use crate::parsing::lines;
+use crate::puzzle::Solution;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleError {
-    #[error("Iterating over line returned `None`.")]
-    EmptyInputDataLine,
-    #[error("Unexpected operation in input line: {}", .0)]
-    UnexpectedOperation(String),
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
+    #[error("Register X overflowed.")]
+    RegisterOverflow,
+    #[error("Signal strength overflowed.")]
+    SignalStrengthOverflow,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -38,170 +43,152 @@ impl Cpu {
         self.cycles_complete + 1
     }
 
-    fn update_signal_strength(&mut self) {
-        match self.current_cycle() {
-            20 | 60 | 100 | 140 | 180 | 220 => {
-                self.total_signal_strength += (self.current_cycle()) * self.x
-            }
-            _ => (),
+    fn update_signal_strength(&mut self) -> Result<(), PuzzleError> {
+        if let 20 | 60 | 100 | 140 | 180 | 220 = self.current_cycle() {
+            self.total_signal_strength = self
+                .current_cycle()
+                .checked_mul(self.x)
+                .and_then(|strength| self.total_signal_strength.checked_add(strength))
+                .ok_or(PuzzleError::SignalStrengthOverflow)?;
         }
+        Ok(())
     }
 
-    fn compute_cycle(&mut self, add_x: isize) {
-        self.update_signal_strength();
+    fn compute_cycle(&mut self, add_x: isize) -> Result<(), PuzzleError> {
+        self.update_signal_strength()?;
         self.cycles_complete += 1;
-        self.x += add_x;
+        self.x = self
+            .x
+            .checked_add(add_x)
+            .ok_or(PuzzleError::RegisterOverflow)?;
+        Ok(())
     }
 
-    fn perform(&mut self, op: &Operation, crt: Option<&mut Crt>) {
+    fn perform(&mut self, op: &Operation, crt: Option<&mut Crt>) -> Result<(), PuzzleError> {
         match op {
             Operation::Noop => {
                 if let Some(_crt) = crt {
-                    _crt.update(self);
+                    _crt.update(self)?;
                 }
                 self.compute_cycle(0)
             }
             Operation::Addx(x) => {
                 let _cpu_capture = *self;
-                self.compute_cycle(0);
+                self.compute_cycle(0)?;
                 if let Some(_crt) = crt {
-                    _crt.update(&_cpu_capture);
-                    _crt.update(self);
+                    _crt.update(&_cpu_capture)?;
+                    _crt.update(self)?;
                 }
-                self.compute_cycle(*x);
+                self.compute_cycle(*x)
             }
-        };
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 struct Crt {
-    dims: (usize, usize), // width x height
-    pixels: Vec<Vec<char>>,
+    pixels: Grid<char>,
 }
 
 impl Crt {
     fn new() -> Self {
-        let dims = (40, 6);
-        let mut pixels = Vec::new();
-        for _ in 0..dims.1 {
-            pixels.push((0..dims.0).map(|_| '.').collect());
+        Crt {
+            pixels: Grid::new(40, 6, '.'),
         }
-        Crt { dims, pixels }
     }
 
     fn _print(&self) {
-        let vbreak = (0..self.dims.0).map(|_| "-").collect::<Vec<_>>().join("");
-        println!("{}", vbreak);
-        for row in self.pixels.iter() {
-            for c in row {
-                print!("{}", c);
-            }
-            println!()
-        }
-        println!("{}", vbreak);
+        let vbreak = "-".repeat(self.pixels.width());
+        println!("{}\n{}\n{}", vbreak, self.pixels, vbreak);
     }
 
     fn display_as_string(&self) -> String {
-        self.pixels
-            .iter()
-            .map(|r| {
-                r.clone()
-                    .iter()
-                    .map(|x| String::from(*x))
-                    .collect::<Vec<_>>()
-                    .join("")
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
-    }
-
-    fn update(&mut self, cpu: &Cpu) {
-        let pixel_col = cpu.cycles_complete % self.dims.0 as isize;
-        if pixel_col < -1 {
-            return;
-        }
-        let pixel_row = (cpu.cycles_complete as f32 / self.dims.0 as f32).floor() as isize;
-        for sprite in (cpu.x - 1)..=(cpu.x + 1) {
-            if sprite == pixel_col {
-                let mut row = self.pixels[pixel_row as usize].clone();
-                row[pixel_col as usize] = '#';
-                self.pixels[pixel_row as usize] = row;
-                return;
+        self.pixels.to_string()
+    }
+
+    fn update(&mut self, cpu: &Cpu) -> Result<(), PuzzleError> {
+        let width = self.pixels.width() as isize;
+        let pixel_col = cpu.cycles_complete % width;
+        let pixel = Position::new((cpu.cycles_complete / width) as usize, pixel_col as usize);
+        let distance = cpu
+            .x
+            .checked_sub(pixel_col)
+            .and_then(isize::checked_abs)
+            .ok_or(PuzzleError::RegisterOverflow)?;
+        if distance <= 1 {
+            // Cycles after the last row have no pixel to draw.
+            if let Some(p) = self.pixels.get_mut(pixel) {
+                *p = '#';
             }
         }
+        Ok(())
     }
 }
 
 pub fn parse_input(input_data: &str) -> Result<Vec<Operation>, PuzzleError> {
     let mut operations = Vec::new();
-    for line in input_data.trim().lines() {
-        let pieces: Vec<&str> = line.trim().split(' ').collect();
-        match pieces.first() {
-            Some(&"noop") => operations.push(Operation::Noop),
-            Some(&"addx") => operations.push(Operation::Addx(
-                pieces[1].to_string().parse::<isize>().unwrap(),
-            )),
-            Some(_) => return Err(PuzzleError::UnexpectedOperation(line.to_string())),
-            None => return Err(PuzzleError::EmptyInputDataLine),
+    for line in lines(input_data) {
+        let line = line.trim();
+        match line.words().next().map(|op| op.text) {
+            Some("noop") => {
+                let [] = line.fields("noop")?;
+                operations.push(Operation::Noop)
+            }
+            Some("addx") => {
+                let [x] = line.fields("addx {}")?;
+                operations.push(Operation::Addx(x.parse()?))
+            }
+            _ => return Err(line.error("expected `noop` or `addx`").into()),
         };
     }
     Ok(operations)
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<isize, PuzzleError> {
-    let operations = parse_input(input_data)?;
-    let mut cpu = Cpu::new();
-    for op in operations {
-        cpu.perform(&op, None);
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Input = Vec<Operation>;
+    type Output1 = isize;
+    type Output2 = String;
+    type Error = PuzzleError;
+
+    fn parse(input_data: &str) -> Result<Vec<Operation>, PuzzleError> {
+        parse_input(input_data)
     }
-    Ok(cpu.total_signal_strength)
-}
 
-pub fn puzzle_2(input_data: &str) -> Result<String, PuzzleError> {
-    let operations = parse_input(input_data)?;
-    let mut cpu = Cpu::new();
-    let mut crt = Crt::new();
-    for op in operations {
-        cpu.perform(&op, Some(&mut crt));
+    fn part_1(operations: &Vec<Operation>) -> Result<isize, PuzzleError> {
+        let mut cpu = Cpu::new();
+        for op in operations {
+            cpu.perform(op, None)?;
+        }
+        Ok(cpu.total_signal_strength)
+    }
+
+    fn part_2(operations: &Vec<Operation>) -> Result<String, PuzzleError> {
+        let mut cpu = Cpu::new();
+        let mut crt = Crt::new();
+        for op in operations {
+            cpu.perform(op, Some(&mut crt))?;
+        }
+        Ok(crt.display_as_string())
     }
-    Ok(crt.display_as_string())
 }
 
-pub fn main(data_dir: &str) {
-    println!("Day 10: Cathode-Ray Tube");
-    let data = load_raw(data_dir, 10, None);
-
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
-    }
-    assert_eq!(answer_1, Ok(15220));
-
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: \n{}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
-    }
-    assert_eq!(
-        answer_2,
-        Ok("###..####.####.####.#..#.###..####..##..
-#..#.#.......#.#....#.#..#..#.#....#..#.
-#..#.###....#..###..##...###..###..#..#.
-###..#.....#...#....#.#..#..#.#....####.
-#.#..#....#....#....#.#..#..#.#....#..#.
-#..#.#....####.####.#..#.###..#....#..#."
-            .to_string())
-    );
+pub fn puzzle_1(input_data: &str) -> Result<isize, PuzzleError> {
+    Day10::part_1(&Day10::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<String, PuzzleError> {
+    Day10::part_2(&Day10::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::data::load_raw;
-    use crate::solutions::day10::{parse_input, puzzle_1, puzzle_2, Cpu};
+    use crate::solutions::day10::{parse_input, puzzle_1, puzzle_2, Cpu, PuzzleError};
 
     const EXAMPLE_1: &str = "
     noop
@@ -220,7 +207,7 @@ mod tests {
         let example_operations = parse_input(EXAMPLE_1).unwrap();
         let mut cpu = Cpu::new();
         for op in example_operations.iter() {
-            cpu.perform(op, None);
+            cpu.perform(op, None).unwrap();
         }
         assert_eq!(cpu.x, -1);
         assert_eq!(cpu.cycles_complete, 5);
@@ -231,13 +218,13 @@ mod tests {
 
     #[test]
     fn puzzle_1_example_2() {
-        let data = load_raw("puzzle-input", 10, Some("_ex1"));
+        let data = load_raw("puzzle-input", 10, Some("_ex1")).unwrap();
         assert_eq!(puzzle_1(&data), Ok(13140));
     }
 
     #[test]
     fn puzzle_2_examples() {
-        let data = load_raw("puzzle-input", 10, Some("_ex1"));
+        let data = load_raw("puzzle-input", 10, Some("_ex1")).unwrap();
         let res = puzzle_2(&data);
         assert_eq!(
             res,
@@ -250,4 +237,13 @@ mod tests {
                 .to_string())
         )
     }
+
+    #[test]
+    fn register_overflow() {
+        let input = "addx 9223372036854775807\naddx 1";
+        assert_eq!(puzzle_1(input), Err(PuzzleError::RegisterOverflow));
+        assert_eq!(puzzle_2(input), Err(PuzzleError::RegisterOverflow));
+        let input = "addx -9223372036854775807\naddx -2\nnoop";
+        assert_eq!(puzzle_2(input), Err(PuzzleError::RegisterOverflow));
+    }
 }
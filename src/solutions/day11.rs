@@ -1,19 +1,21 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::parsing::{blocks, end, Span};
+use crate::puzzle::Solution;
 use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum PuzzleError {
-    #[error("Failed parsing integer.")]
-    ParseIntError(#[from] std::num::ParseIntError),
-    #[error("String parsing error: {}", .0)]
-    StringParsingError(String),
-    #[error("Unrecognized math operator: {}", .0)]
-    UnrecognizedMathOperator(String),
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
     #[error("Division operator is not supported because rounding is unspecified.")]
     UnclearHowToRoundDivision,
     #[error("No monkey with ID {}.", .0)]
     NoMonkeyWithId(usize),
+    #[error("Worry level of an item overflowed.")]
+    WorryOverflow,
+    #[error("Monkey business needs at least 2 monkeys, found {}.", .0)]
+    TooFewMonkeys(usize),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -23,8 +25,8 @@ enum OperationVar {
 }
 
 impl OperationVar {
-    fn identify_var(x: &str) -> Result<Self, PuzzleError> {
-        if x == "old" {
+    fn identify_var(x: Span) -> Result<Self, PuzzleError> {
+        if x.text == "old" {
             return Ok(OperationVar::Old);
         }
         let parsed_val = x.parse::<isize>()?;
@@ -41,21 +43,21 @@ enum MathOperator {
 }
 
 impl MathOperator {
-    fn identify_op(op_str: &str) -> Result<Self, PuzzleError> {
-        match op_str.trim() {
+    fn identify_op(op_str: Span) -> Result<Self, PuzzleError> {
+        match op_str.text {
             "+" => Ok(MathOperator::Add),
             "-" => Ok(MathOperator::Subtract),
             "*" => Ok(MathOperator::Multiply),
             "/" => Ok(MathOperator::Divide),
-            _ => Err(PuzzleError::UnrecognizedMathOperator(op_str.to_string())),
+            _ => Err(op_str.error("expected `+`, `-`, `*` or `/`").into()),
         }
     }
 
     fn do_math(&self, x: &isize, y: &isize) -> Result<isize, PuzzleError> {
         match self {
-            MathOperator::Add => Ok(x + y),
-            MathOperator::Subtract => Ok(x - y),
-            MathOperator::Multiply => Ok(x * y),
+            MathOperator::Add => x.checked_add(*y).ok_or(PuzzleError::WorryOverflow),
+            MathOperator::Subtract => x.checked_sub(*y).ok_or(PuzzleError::WorryOverflow),
+            MathOperator::Multiply => x.checked_mul(*y).ok_or(PuzzleError::WorryOverflow),
             MathOperator::Divide => Err(PuzzleError::UnclearHowToRoundDivision),
         }
     }
@@ -69,11 +71,11 @@ pub struct MonkeyOperation {
 }
 
 impl MonkeyOperation {
-    fn from_str(input: &str) -> Result<Self, PuzzleError> {
-        let split_input = input.trim().split(' ').collect::<Vec<_>>();
-        let x = OperationVar::identify_var(split_input[0])?;
-        let y = OperationVar::identify_var(split_input[2])?;
-        let op = MathOperator::identify_op(split_input[1])?;
+    fn from_span(input: Span) -> Result<Self, PuzzleError> {
+        let [x, op, y] = input.fields("{} {} {}")?;
+        let x = OperationVar::identify_var(x)?;
+        let y = OperationVar::identify_var(y)?;
+        let op = MathOperator::identify_op(op)?;
         Ok(MonkeyOperation { x, y, op })
     }
 
@@ -90,7 +92,7 @@ impl MonkeyOperation {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct MonkeyDecision {
     receiver_monkey_id: usize,
     item_value: isize,
@@ -153,7 +155,8 @@ impl Monkey {
             log::debug!("Starting worry level: {}.", item);
             let post_inspection_val = self.operation.perform(item)?;
             log::debug!("Post-inspection worry level: {}", post_inspection_val);
-            let worry_reduced_val = (post_inspection_val as f32 / 3.0).floor() as isize;
+            // Floor, not truncate, so that negative worry levels round down as well.
+            let worry_reduced_val = post_inspection_val.div_euclid(3);
             log::debug!("Reduced worry level: {}", worry_reduced_val);
             let receiving_monkey = match worry_reduced_val % self.test_division_value {
                 0 => {
@@ -243,19 +246,20 @@ impl Monkeys {
         Ok(())
     }
 
-    fn monkey_divisor(&self) -> isize {
+    fn monkey_divisor(&self) -> Result<isize, PuzzleError> {
         self.monkeys
             .values()
-            .map(|m| m.test_division_value)
-            .product()
+            .try_fold(1isize, |div, m| div.checked_mul(m.test_division_value))
+            .ok_or(PuzzleError::WorryOverflow)
     }
 
-    fn reduce_all_monkey_values(&mut self) {
+    fn reduce_all_monkey_values(&mut self) -> Result<(), PuzzleError> {
         log::info!("Reducing monkey values.");
-        let div = self.monkey_divisor();
+        let div = self.monkey_divisor()?;
         for monkey in self.monkeys.values_mut() {
             monkey.items = monkey.items.iter().map(|x| *x % div).collect();
         }
+        Ok(())
     }
 
     fn perform_round(
@@ -276,113 +280,124 @@ impl Monkeys {
             self.disperse_results(&decision_results)?;
         }
         if !div_by_3 {
-            self.reduce_all_monkey_values()
+            self.reduce_all_monkey_values()?;
         }
         Ok(())
     }
 }
 
-fn extract_and_parse_last_word<T: std::str::FromStr>(s: &str) -> Result<T, PuzzleError> {
-    s.split(' ')
-        .last()
-        .ok_or(PuzzleError::StringParsingError(s.to_string()))?
-        .parse::<T>()
-        .or(Err(PuzzleError::StringParsingError(s.to_string())))
-}
-
 pub fn parse_input(input_data: &str) -> Result<Monkeys, PuzzleError> {
     let mut monkeys = Monkeys::new();
-    for lines in input_data
-        .trim()
-        .lines()
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-        .collect::<Vec<_>>()
-        .windows(6)
-        .step_by(6)
-    {
-        // Extract ID.
-        let id: usize = extract_and_parse_last_word(&lines[0].replace(':', ""))?;
-
-        // Extract starting items.
-        let items_string = lines[1].replace("Starting items: ", "");
-        let items = items_string
-            .split(", ")
-            .map(|x| x.parse::<isize>())
-            .collect::<Result<Vec<_>, _>>()?;
-        // Extract operation.
-        let operation_str = lines[2].replace("Operation: new = ", "").to_string();
-        let operation = MonkeyOperation::from_str(&operation_str)?;
-        // Extract division test.
-        let test_division_value: isize = extract_and_parse_last_word(lines[3])?;
-        // Extract true result.
-        let true_monkey: usize = extract_and_parse_last_word(lines[4])?;
-        // Extract false result.
-        let false_monkey: usize = extract_and_parse_last_word(lines[5])?;
+    let mut targets = Vec::new();
+    let mut headers = HashMap::new();
+    let blocks = blocks(input_data);
+    if blocks.len() < 2 {
+        let message = format!("expected at least 2 monkeys, found {}", blocks.len());
+        return Err(end(input_data).error(message).into());
+    }
+    for block in blocks {
+        let [id, items, operation, test, if_true, if_false] = block[..] else {
+            let message = format!("expected 6 lines about a monkey, found {}", block.len());
+            return Err(block[0].error(message).into());
+        };
+        let [id] = id.fields("Monkey {}:")?;
+        let id_value = id.parse()?;
+        if let Some(first_line) = headers.insert(id_value, id.line) {
+            let message = format!(
+                "monkey {} is already described on line {}",
+                id_value, first_line
+            );
+            return Err(id.error(message).into());
+        }
+        let items = items.after("Starting items:")?.ints()?;
+        let operation_span = operation.after("Operation: new =")?;
+        let operation = MonkeyOperation::from_span(operation_span)?;
+        let [test_division_value] = test.fields("Test: divisible by {}")?;
+        let [true_monkey] = if_true.fields("If true: throw to monkey {}")?;
+        let [false_monkey] = if_false.fields("If false: throw to monkey {}")?;
+        let divisor = test_division_value.parse()?;
+        if divisor <= 0 {
+            return Err(test_division_value
+                .error("expected a positive divisor")
+                .into());
+        }
+        targets.extend([true_monkey, false_monkey]);
 
         monkeys.new_monkey(&Monkey {
-            id,
+            id: id_value,
             items,
-            _operation_str: operation_str,
+            _operation_str: operation_span.text.to_string(),
             operation,
-            test_division_value,
-            true_monkey,
-            false_monkey,
+            test_division_value: divisor,
+            true_monkey: true_monkey.parse()?,
+            false_monkey: false_monkey.parse()?,
         });
     }
-    Ok(monkeys)
-}
-
-pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
-    let mut monkeys = parse_input(input_data)?;
-    let mut item_counter = HashMap::new();
-    for i in 0..20 {
-        log::info!("Round {}", i);
-        monkeys.perform_round(&mut item_counter, true)?;
+    for target in targets {
+        let id = target.parse()?;
+        if !monkeys.monkeys.contains_key(&id) {
+            return Err(target.error(format!("there is no monkey {}", id)).into());
+        }
     }
-    let mut item_counts = item_counter.values().collect::<Vec<_>>();
-    item_counts.sort();
-    item_counts.reverse();
-    Ok(item_counts[0] * item_counts[1])
+    Ok(monkeys)
 }
 
-pub fn puzzle_2(input_data: &str, n_rounds: usize) -> Result<usize, PuzzleError> {
-    let mut monkeys = parse_input(input_data)?;
+fn monkey_business(
+    monkeys: &Monkeys,
+    n_rounds: usize,
+    div_by_3: bool,
+) -> Result<usize, PuzzleError> {
+    let mut monkeys = monkeys.clone();
     let mut item_counter = HashMap::new();
     for i in 0..n_rounds {
         log::info!("Round {}", i);
-        monkeys.perform_round(&mut item_counter, false)?;
+        monkeys.perform_round(&mut item_counter, div_by_3)?;
     }
     let mut item_counts = item_counter.values().collect::<Vec<_>>();
     item_counts.sort();
     item_counts.reverse();
-    Ok(item_counts[0] * item_counts[1])
+    match item_counts[..] {
+        [first, second, ..] => first.checked_mul(*second).ok_or(PuzzleError::WorryOverflow),
+        _ => Err(PuzzleError::TooFewMonkeys(item_counts.len())),
+    }
 }
 
-pub fn main(data_dir: &str) {
-    println!("Day 11: Monkey in the Middle");
-    let data = load_raw(data_dir, 11, None);
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+    const VERSION: u32 = 2;
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    type Input = Monkeys;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
+
+    fn parse(input_data: &str) -> Result<Monkeys, PuzzleError> {
+        parse_input(input_data)
+    }
+
+    fn part_1(monkeys: &Monkeys) -> Result<usize, PuzzleError> {
+        monkey_business(monkeys, 20, true)
     }
-    assert_eq!(answer_1, Ok(113232));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data, 10000);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
+    fn part_2(monkeys: &Monkeys) -> Result<usize, PuzzleError> {
+        monkey_business(monkeys, 10000, false)
     }
-    assert_eq!(answer_2, Ok(29703395016));
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day11::part_1(&Day11::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str, n_rounds: usize) -> Result<usize, PuzzleError> {
+    monkey_business(&Day11::parse(input_data)?, n_rounds, false)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day11::{puzzle_1, puzzle_2};
+    use crate::solutions::day11::{parse_input, puzzle_1, puzzle_2, MonkeyDecision, PuzzleError};
 
     const EXAMPLE_1: &str = "
     Monkey 0:
@@ -414,12 +429,42 @@ mod tests {
         If false: throw to monkey 1
     ";
 
+    // The item of monkey 0 is only divisible after dividing by 3 exactly, which `f32` cannot
+    // do for numbers this large.
+    const LARGE_WORRY_LEVEL: &str = "
+    Monkey 0:
+    Starting items: 50000003
+    Operation: new = old + 0
+    Test: divisible by 16666667
+        If true: throw to monkey 1
+        If false: throw to monkey 2
+
+    Monkey 1:
+    Starting items: 1
+    Operation: new = old + 0
+    Test: divisible by 1
+        If true: throw to monkey 2
+        If false: throw to monkey 2
+
+    Monkey 2:
+    Starting items: 1
+    Operation: new = old + 0
+    Test: divisible by 1
+        If true: throw to monkey 1
+        If false: throw to monkey 1
+    ";
+
     #[test]
     fn puzzle_1_example() {
         let res = puzzle_1(EXAMPLE_1);
         assert_eq!(res, Ok(10605));
     }
 
+    #[test]
+    fn puzzle_1_large_worry_level() {
+        assert_eq!(puzzle_1(LARGE_WORRY_LEVEL), Ok(3540));
+    }
+
     #[test]
     fn puzzle_2_example_n1() {
         let res: Result<usize, crate::solutions::day11::PuzzleError> = puzzle_2(EXAMPLE_1, 1);
@@ -437,4 +482,55 @@ mod tests {
         let res = puzzle_2(EXAMPLE_1, 10000);
         assert_eq!(res, Ok(2713310158));
     }
+
+    #[test]
+    fn notes_that_cannot_be_played() {
+        let problem = |input: &str| match puzzle_1(input) {
+            Err(PuzzleError::Parsing(diagnostic)) => diagnostic.summary(),
+            other => panic!("expected a parsing error, got {:?}", other),
+        };
+        assert_eq!(
+            problem(&EXAMPLE_1.replace("divisible by 13", "divisible by 0")),
+            "line 19, column 24: expected a positive divisor"
+        );
+        assert_eq!(
+            problem(&EXAMPLE_1.replace("monkey 3", "monkey 4")),
+            "line 7, column 35: there is no monkey 4"
+        );
+        assert_eq!(
+            problem(EXAMPLE_1.split("\n\n").next().unwrap()),
+            "line 7, column 36: expected at least 2 monkeys, found 1"
+        );
+        assert_eq!(
+            problem(&EXAMPLE_1.replace("Monkey 2:", "Monkey 0:")),
+            "line 16, column 12: monkey 0 is already described on line 2"
+        );
+        let squared = EXAMPLE_1.replace("79, 60, 97", "4294967296");
+        assert_eq!(puzzle_1(&squared), Err(PuzzleError::WorryOverflow));
+    }
+
+    #[test]
+    fn negative_worry_rounds_down() {
+        let input = "
+Monkey 0:
+  Starting items: -4, 4
+  Operation: new = old * 1
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 0
+
+Monkey 1:
+  Starting items: 1
+  Operation: new = old * 1
+  Test: divisible by 2
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+        let mut monkeys = parse_input(input).unwrap();
+        let decisions = monkeys.monkeys.get_mut(&0).unwrap().inspect_items_1();
+        assert_eq!(
+            decisions,
+            Ok(vec![MonkeyDecision::new(1, -2), MonkeyDecision::new(0, 1)])
+        );
+    }
 }
@@ -1,4 +1,7 @@
-use crate::data::load_raw;
+use crate::diagnostic::Diagnostic;
+use crate::grid::{Grid, Position};
+use crate::parsing::{char_grid, lines};
+use crate::puzzle::Solution;
 use petgraph::algo::k_shortest_path;
 use petgraph::graph::DiGraph;
 use petgraph::graph::NodeIndex;
@@ -10,26 +13,14 @@ use thiserror::Error;
 pub enum PuzzleError {
     #[error("Failed parsing integer.")]
     ParseIntError(#[from] std::num::ParseIntError),
-    #[error("Unkown height: {}", .0)]
-    UnknownHeight(String),
     #[error("No start coordinate.")]
     NoStartCoord,
     #[error("No end coordinate.")]
     NoEndCoord,
     #[error("No paths found.")]
     NoPathsFound,
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord {
-    r: usize,
-    c: usize,
-}
-
-impl std::fmt::Display for Coord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{},{}]", self.r, self.c)
-    }
+    #[error(transparent)]
+    Parsing(#[from] Diagnostic),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -58,21 +49,8 @@ impl std::fmt::Display for Height {
 }
 
 #[derive(Debug, Clone)]
-struct HeightMap {
-    heights: HashMap<Coord, Height>,
-}
-
-impl HeightMap {
-    fn new() -> Self {
-        HeightMap {
-            heights: HashMap::new(),
-        }
-    }
-
-    fn add_value(&mut self, row: usize, col: usize, value: Height) {
-        log::debug!("Adding value: [{},{}] -> {}", row, col, value);
-        self.heights.insert(Coord { r: row, c: col }, value);
-    }
+pub struct HeightMap {
+    heights: Grid<Height>,
 }
 
 struct HeightTranslator {
@@ -88,81 +66,43 @@ impl HeightTranslator {
         Self { score_map }
     }
 
-    fn translate(&self, input: &char) -> Result<Height, PuzzleError> {
+    fn translate(&self, input: &char) -> Option<Height> {
         match input {
-            'S' => Ok(Height::Start(
-                self.score_map
-                    .get(&'a')
-                    .cloned()
-                    .ok_or(PuzzleError::UnknownHeight(input.to_string()))?,
-            )),
-            'E' => Ok(Height::End(
-                self.score_map
-                    .get(&'z')
-                    .cloned()
-                    .ok_or(PuzzleError::UnknownHeight(input.to_string()))?,
-            )),
-            x => {
-                let val = self
-                    .score_map
-                    .get(x)
-                    .cloned()
-                    .ok_or(PuzzleError::UnknownHeight(input.to_string()))?;
-                Ok(Height::H(val))
-            }
+            'S' => Some(Height::Start(self.score_map.get(&'a').cloned()?)),
+            'E' => Some(Height::End(self.score_map.get(&'z').cloned()?)),
+            x => Some(Height::H(self.score_map.get(x).cloned()?)),
         }
     }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
-struct Position {
+struct Node {
     height: Height,
-    coord: Coord,
-}
-
-struct Nodes {
-    nodes: HashMap<Position, NodeIndex>,
-}
-
-impl Nodes {
-    fn new() -> Self {
-        Nodes {
-            nodes: HashMap::new(),
-        }
-    }
-
-    fn get(&mut self, pos: &Position, tree: &mut DiGraph<Position, &str>) -> NodeIndex {
-        match self.nodes.get(pos) {
-            Some(i) => *i,
-            None => {
-                let i = tree.add_node(pos.clone());
-                self.nodes.insert(pos.clone(), i);
-                i
-            }
-        }
-    }
+    pos: Position,
 }
 
 #[derive(Debug, Clone)]
 struct HeightTree<'a> {
-    tree: DiGraph<Position, &'a str>,
+    tree: DiGraph<Node, &'a str>,
     start: NodeIndex,
     end: NodeIndex,
 }
 
 impl<'a> HeightTree<'a> {
     fn from_height_map(height_map: &HeightMap) -> Result<Self, PuzzleError> {
+        let heights = &height_map.heights;
         let mut tree = DiGraph::new();
+        let nodes = heights.map(|pos, height| {
+            tree.add_node(Node {
+                height: *height,
+                pos,
+            })
+        });
         let mut start: Option<NodeIndex> = Option::None;
         let mut end: Option<NodeIndex> = Option::None;
-        let mut nodes = Nodes::new();
 
-        for (coord, height) in height_map.heights.iter() {
-            let p = Position {
-                height: *height,
-                coord: *coord,
-            };
-            let node_idx = nodes.get(&p, &mut tree);
+        for (pos, height) in heights.iter() {
+            let node_idx = nodes[pos];
 
             // Record Start and End nodes.
             match height {
@@ -174,25 +114,9 @@ impl<'a> HeightTree<'a> {
                 _ => (),
             }
 
-            for dir in [(1, 0), (-1, 0), (0, 1), (0, -1)].iter() {
-                let new_r = coord.r as isize + dir.0;
-                let new_c = coord.c as isize + dir.1;
-                if (new_r < 0) | (new_c < 0) {
-                    continue;
-                }
-                let neighbor_coord = Coord {
-                    r: new_r as usize,
-                    c: new_c as usize,
-                };
-                if let Some(neighbor_height) = height_map.heights.get(&neighbor_coord) {
-                    if neighbor_height.get_height() <= (height.get_height() + 1) {
-                        let neighbor_pos = Position {
-                            height: *neighbor_height,
-                            coord: neighbor_coord,
-                        };
-                        let neighbor_idx = nodes.get(&neighbor_pos, &mut tree);
-                        tree.add_edge(node_idx, neighbor_idx, "");
-                    }
+            for (neighbor_pos, neighbor_height) in heights.neighbours_4(pos) {
+                if neighbor_height.get_height() <= (height.get_height() + 1) {
+                    tree.add_edge(node_idx, nodes[neighbor_pos], "");
                 }
             }
         }
@@ -213,61 +137,79 @@ impl<'a> HeightTree<'a> {
 }
 
 fn parse_input(input_data: &str) -> Result<HeightMap, PuzzleError> {
-    let mut heightmap = HeightMap::new();
     let height_translator = HeightTranslator::new();
-    for (row, line) in input_data.trim().lines().map(|a| a.trim()).enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            heightmap.add_value(row, col, height_translator.translate(&c)?)
+    let heights = char_grid(input_data, "a height from `a` to `z`, `S` or `E`", |c| {
+        height_translator.translate(&c)
+    })?;
+    let mut markers = HashMap::new();
+    for (span, c) in lines(input_data)
+        .iter()
+        .flat_map(|line| line.trim().chars())
+    {
+        let name = match c {
+            'S' => "start",
+            'E' => "end",
+            _ => continue,
+        };
+        if let Some(first) = markers.insert(c, span) {
+            let message = format!(
+                "another {} `{}`, the first is on line {}, column {}",
+                name, c, first.line, first.column
+            );
+            return Err(span.error(message).into());
         }
     }
-    Ok(heightmap)
+    Ok(HeightMap { heights })
 }
 
-pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
-    let height_tree = HeightTree::from_height_map(&parse_input(input_data)?)?;
-    height_tree.shortest_distance()
-}
+pub struct Day12;
 
-pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
-    let mut height_tree = HeightTree::from_height_map(&parse_input(input_data)?)?;
-    height_tree.tree.reverse(); // Reverse and go from E to all nodes with height "a".
-    height_tree
-        .tree
-        .node_indices()
-        .filter(|i| height_tree.tree[*i].height.get_height() == 0)
-        .filter_map(|i| {
-            k_shortest_path(&height_tree.tree, height_tree.end, Some(i), 1, |_| 1)
-                .get(&i)
-                .cloned()
-        })
-        .min()
-        .ok_or(PuzzleError::NoPathsFound)
-}
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
 
-pub fn main(data_dir: &str) {
-    println!("Day 12: Hill Climbing Algorithm");
-    let data = load_raw(data_dir, 12, None);
+    type Input = HeightMap;
+    type Output1 = usize;
+    type Output2 = usize;
+    type Error = PuzzleError;
 
-    // Puzzle 1.
-    let answer_1 = puzzle_1(&data);
-    match &answer_1 {
-        Ok(x) => println!(" Puzzle 1: {}", x),
-        Err(e) => panic!("Error on Puzzle 1: {}", e),
+    fn parse(input_data: &str) -> Result<HeightMap, PuzzleError> {
+        parse_input(input_data)
     }
-    assert_eq!(answer_1, Ok(447));
 
-    // Puzzle 2.
-    let answer_2 = puzzle_2(&data);
-    match &answer_2 {
-        Ok(x) => println!(" Puzzle 2: {}", x),
-        Err(e) => panic!("Error on Puzzle 2: {}", e),
+    fn part_1(height_map: &HeightMap) -> Result<usize, PuzzleError> {
+        let height_tree = HeightTree::from_height_map(height_map)?;
+        height_tree.shortest_distance()
     }
-    assert_eq!(answer_2, Ok(446));
+
+    fn part_2(height_map: &HeightMap) -> Result<usize, PuzzleError> {
+        let mut height_tree = HeightTree::from_height_map(height_map)?;
+        height_tree.tree.reverse(); // Reverse and go from E to all nodes with height "a".
+        height_tree
+            .tree
+            .node_indices()
+            .filter(|i| height_tree.tree[*i].height.get_height() == 0)
+            .filter_map(|i| {
+                k_shortest_path(&height_tree.tree, height_tree.end, Some(i), 1, |_| 1)
+                    .get(&i)
+                    .cloned()
+            })
+            .min()
+            .ok_or(PuzzleError::NoPathsFound)
+    }
+}
+
+pub fn puzzle_1(input_data: &str) -> Result<usize, PuzzleError> {
+    Day12::part_1(&Day12::parse(input_data)?)
+}
+
+pub fn puzzle_2(input_data: &str) -> Result<usize, PuzzleError> {
+    Day12::part_2(&Day12::parse(input_data)?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::solutions::day12::{puzzle_1, puzzle_2};
+    use crate::solutions::day12::{puzzle_1, puzzle_2, PuzzleError};
 
     const EXAMPLE_1: &str = "
     Sabqponm
@@ -279,15 +221,31 @@ mod tests {
 
     #[test]
     fn puzzle_1_example_1() {
-        env_logger::init();
+        let _ = env_logger::try_init();
         let res = puzzle_1(EXAMPLE_1);
         assert_eq!(res, Ok(31));
     }
 
     #[test]
     fn puzzle_2_example_1() {
-        env_logger::init();
+        let _ = env_logger::try_init();
         let res = puzzle_2(EXAMPLE_1);
         assert_eq!(res, Ok(29));
     }
+
+    #[test]
+    fn one_start_and_one_end() {
+        let problem = |input: &str| match puzzle_1(input) {
+            Err(PuzzleError::Parsing(diagnostic)) => diagnostic.summary(),
+            other => panic!("expected a parsing error, got {:?}", other),
+        };
+        assert_eq!(
+            problem("SabS\nabcE"),
+            "line 1, column 4: another start `S`, the first is on line 1, column 1"
+        );
+        assert_eq!(
+            problem("SabE\nEbcd"),
+            "line 2, column 1: another end `E`, the first is on line 1, column 4"
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::puzzle::Puzzle;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -10,3 +12,31 @@ pub mod day09;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+
+/// Add the solutions of the day modules to the registry.
+macro_rules! register_days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        /// All solved days, in calendar order.
+        pub static DAYS: &[&dyn Puzzle] = &[$(&$module::$solution),*];
+    };
+}
+
+register_days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+}
+
+/// Look up the solution for a day.
+pub fn get(day: u32) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().find(|p| p.day() == day).copied()
+}
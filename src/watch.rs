@@ -0,0 +1,144 @@
+use crate::answer::Answer;
+use crate::data::{input_name, input_suffixes};
+use crate::report::{DayReport, Failure};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time of each watched file, or none if it does not exist.
+pub type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Files to watch for a day: every `NN*.txt` input in the data directory, plus a custom
+/// input file if one is given.
+pub fn watched_files(data_dir: &str, day: u32, input: Option<&str>) -> Vec<PathBuf> {
+    let mut files = input_suffixes(data_dir, day)
+        .unwrap_or_default()
+        .iter()
+        .map(|suffix| {
+            PathBuf::from(format!(
+                "{}/{}.txt",
+                data_dir,
+                input_name(day, Some(suffix))
+            ))
+        })
+        .collect::<Vec<_>>();
+    files.extend(input.map(PathBuf::from));
+    files
+}
+
+pub fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .map(|file| {
+            let modified = fs::metadata(file).and_then(|m| m.modified()).ok();
+            (file.clone(), modified)
+        })
+        .collect()
+}
+
+/// Call `on_change` now and again every time the set of files or one of their
+/// modification times changes. The files are listed again on every poll so that new
+/// inputs are picked up. Runs until the process is stopped.
+pub fn watch(files: impl Fn() -> Vec<PathBuf>, mut on_change: impl FnMut()) -> ! {
+    let mut last = snapshot(&files());
+    on_change();
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let current = snapshot(&files());
+        if current != last {
+            last = current;
+            on_change();
+        }
+    }
+}
+
+fn describe(answer: &Result<Answer, Failure>) -> String {
+    match answer {
+        Ok(a) if a.is_multiline() => String::from("(picture)"),
+        Ok(a) => a.to_string(),
+        Err(e) => e.to_string().lines().next().unwrap_or_default().to_string(),
+    }
+}
+
+fn answers(reports: &[DayReport]) -> BTreeMap<(String, u8), &Result<Answer, Failure>> {
+    reports
+        .iter()
+        .flat_map(|r| {
+            r.parts
+                .iter()
+                .map(|p| ((r.input.clone(), p.part), &p.answer))
+        })
+        .collect()
+}
+
+/// How the answers of each input and part changed since the previous run.
+pub fn answer_diff(previous: &[DayReport], current: &[DayReport]) -> Vec<String> {
+    let before = answers(previous);
+    let after = answers(current);
+
+    let mut keys = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .map(|key| {
+            let (input, part) = key;
+            let change = match (before.get(key), after.get(key)) {
+                (Some(old), Some(new)) if old == new => String::from("unchanged"),
+                (Some(old), Some(new)) => match (old, new) {
+                    (Ok(a), Ok(b)) if a.is_multiline() & b.is_multiline() => {
+                        String::from("picture changed")
+                    }
+                    _ => format!("{} -> {}", describe(old), describe(new)),
+                },
+                (None, Some(new)) => format!("new: {}", describe(new)),
+                (Some(old), None) => format!("no longer run (was {})", describe(old)),
+                (None, None) => unreachable!("Every key comes from one of the runs."),
+            };
+            format!("{} puzzle {}: {}", input, part, change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solutions::get;
+    use crate::solve;
+    use crate::watch::{answer_diff, snapshot, watched_files};
+    use std::path::PathBuf;
+
+    #[test]
+    fn files_to_watch() {
+        assert_eq!(
+            watched_files("puzzle-input", 10, Some("my-input.txt")),
+            vec![
+                PathBuf::from("puzzle-input/10.txt"),
+                PathBuf::from("puzzle-input/10_ex1.txt"),
+                PathBuf::from("my-input.txt"),
+            ]
+        );
+        let snap = snapshot(&watched_files("puzzle-input", 10, Some("my-input.txt")));
+        assert!(snap[&PathBuf::from("puzzle-input/10.txt")].is_some());
+        assert!(snap[&PathBuf::from("my-input.txt")].is_none());
+    }
+
+    #[test]
+    fn diff_answers() {
+        let mut before = solve(get(1).unwrap(), "1000\n2000\n\n3000");
+        let mut after = solve(get(1).unwrap(), "1000\n2000\n\n4000");
+        before.input = String::from("01");
+        after.input = String::from("01");
+        assert_eq!(
+            answer_diff(&[before.clone()], &[after]),
+            vec!["01 puzzle 1: 3000 -> 4000", "01 puzzle 2: 6000 -> 7000"]
+        );
+        assert_eq!(
+            answer_diff(&[before.clone()], &[before]),
+            vec!["01 puzzle 1: unchanged", "01 puzzle 2: unchanged"]
+        );
+    }
+}
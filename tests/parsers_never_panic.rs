@@ -0,0 +1,213 @@
+//! Feed malformed text to the parser of every registered day and check that it returns an
+//! error instead of panicking, and that both parts return an answer or an error for every
+//! input that parses. Inputs are random printable text, soups of the words and symbols the
+//! puzzle formats are made of, and generated puzzle inputs with random edits.
+
+use advent_of_code_2022_rust::inputgen;
+use advent_of_code_2022_rust::solutions::DAYS;
+use proptest::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Pieces of the puzzle formats, so that random text gets past the first checks of the
+/// parsers more often than fully random text would.
+const TOKENS: &[&str] = &[
+    "\n",
+    "\n\n",
+    " ",
+    "  ",
+    "    ",
+    "-",
+    ",",
+    ":",
+    ", ",
+    "0",
+    "1",
+    "2",
+    "7",
+    "9",
+    "10",
+    "-3",
+    "99999999999999999999",
+    "A",
+    "B",
+    "C",
+    "X",
+    "Y",
+    "Z",
+    "a",
+    "z",
+    "S",
+    "E",
+    "[A]",
+    "[Z]",
+    "   ",
+    " 1   2 ",
+    "move",
+    "from",
+    "to",
+    "$",
+    "cd",
+    "/",
+    "..",
+    "ls",
+    "dir",
+    "f.txt",
+    "U",
+    "D",
+    "L",
+    "R",
+    "noop",
+    "addx",
+    "Monkey",
+    "Starting items:",
+    "Operation: new =",
+    "old",
+    "*",
+    "+",
+    "Test: divisible by",
+    "If true: throw to monkey",
+    "If false: throw to monkey",
+];
+
+/// The largest and smallest values of the integer types the days parse numbers into.
+const BOUNDARIES: &[&str] = &[
+    "4294967295",
+    "18446744073709551615",
+    "9223372036854775807",
+    "-9223372036854775808",
+];
+
+fn token_soup() -> impl Strategy<Value = String> {
+    let tokens = TOKENS.iter().chain(BOUNDARIES).copied().collect::<Vec<_>>();
+    prop::collection::vec(prop::sample::select(tokens), 0..80).prop_map(|tokens| tokens.concat())
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete(usize, usize),
+    Insert(usize, &'static str),
+    DuplicateLine(usize),
+    Truncate(usize),
+    ReplaceNumber(usize, &'static str),
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        (any::<usize>(), 1..20usize).prop_map(|(at, n)| Edit::Delete(at, n)),
+        (any::<usize>(), prop::sample::select(TOKENS)).prop_map(|(at, t)| Edit::Insert(at, t)),
+        any::<usize>().prop_map(Edit::DuplicateLine),
+        any::<usize>().prop_map(Edit::Truncate),
+        (any::<usize>(), prop::sample::select(BOUNDARIES))
+            .prop_map(|(i, n)| Edit::ReplaceNumber(i, n)),
+    ]
+}
+
+fn apply(input: &str, edit: &Edit) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let at = |i: usize| i % (chars.len() + 1);
+    match edit {
+        Edit::Delete(i, n) => {
+            let start = at(*i);
+            chars.drain(start..(start + n).min(chars.len()));
+        }
+        Edit::Insert(i, token) => {
+            let start = at(*i);
+            chars.splice(start..start, token.chars());
+        }
+        Edit::DuplicateLine(i) => {
+            let mut lines = input.lines().collect::<Vec<_>>();
+            if !lines.is_empty() {
+                let line = lines[i % lines.len()];
+                lines.insert(i % lines.len(), line);
+            }
+            return lines.join("\n");
+        }
+        Edit::Truncate(i) => chars.truncate(at(*i)),
+        Edit::ReplaceNumber(i, number) => {
+            let numbers = input
+                .match_indices(|c: char| c.is_ascii_digit() || c == '-')
+                .map(|(start, _)| start)
+                .filter(|start| !input[..*start].ends_with(|c: char| c.is_ascii_digit()))
+                .collect::<Vec<_>>();
+            if numbers.is_empty() {
+                return input.to_string();
+            }
+            let start = numbers[i % numbers.len()];
+            let end = start
+                + 1
+                + input[start + 1..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(input.len() - start - 1);
+            return format!("{}{}{}", &input[..start], number, &input[end..]);
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// A generated input for one of the days, with a few random edits.
+fn edited_input() -> impl Strategy<Value = String> {
+    let days = inputgen::days();
+    (
+        prop::sample::select(days),
+        any::<u64>(),
+        prop::collection::vec(edit(), 1..6),
+    )
+        .prop_map(|(day, seed, edits)| {
+            let input = inputgen::generate(day, seed).unwrap_or_default();
+            edits.iter().fold(input, |input, edit| apply(&input, edit))
+        })
+}
+
+/// Days whose parser, or one of whose parts, panicked on the input.
+fn panicking_days(input: &str) -> Vec<u32> {
+    DAYS.iter()
+        .filter(|puzzle| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                if let Ok(parsed) = puzzle.parse(input) {
+                    let _ = puzzle.part_1(&parsed);
+                    let _ = puzzle.part_2(&parsed);
+                }
+            }))
+            .is_err()
+        })
+        .map(|puzzle| puzzle.day())
+        .collect()
+}
+
+/// Inputs that once made a day panic. The inputs are kept here because the seeds in
+/// `parsers_never_panic.proptest-regressions` only replay the random cases that found them.
+const REGRESSIONS: &[&str] = &[
+    "4294967295\n\n1",
+    "addx 9223372036854775807\naddx 1",
+    "addx -9223372036854775808\nnoop",
+];
+
+#[test]
+fn past_panics() {
+    for input in REGRESSIONS {
+        let days = panicking_days(input);
+        assert!(days.is_empty(), "days {:?} panicked on {:?}", days, input);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    #[test]
+    fn printable_text(input in "[ -~\n]{0,200}") {
+        let days = panicking_days(&input);
+        prop_assert!(days.is_empty(), "days {:?} panicked", days);
+    }
+
+    #[test]
+    fn format_tokens(input in token_soup()) {
+        let days = panicking_days(&input);
+        prop_assert!(days.is_empty(), "days {:?} panicked", days);
+    }
+
+    #[test]
+    fn edited_puzzle_inputs(input in edited_input()) {
+        let days = panicking_days(&input);
+        prop_assert!(days.is_empty(), "days {:?} panicked", days);
+    }
+}
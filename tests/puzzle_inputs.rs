@@ -0,0 +1,54 @@
+//! Run every registered day on each of its inputs in `puzzle-input/` and check the answers
+//! against `puzzle-input/answers.toml`. Days without an input file are skipped.
+
+use advent_of_code_2022_rust::answers::{AnswerKey, Verdict};
+use advent_of_code_2022_rust::solutions::DAYS;
+use advent_of_code_2022_rust::{run_day_variants, RunOptions};
+use std::thread;
+
+const DATA_DIR: &str = "puzzle-input";
+
+#[test]
+fn answers_match_expected() {
+    let key = AnswerKey::load(DATA_DIR).unwrap();
+    let options = RunOptions {
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        ..RunOptions::default()
+    };
+
+    let mut problems = Vec::new();
+    let mut checked = 0;
+    for puzzle in DAYS.iter() {
+        let mut reports = run_day_variants(DATA_DIR, &(puzzle.day() as usize), &options).unwrap();
+        if reports.is_empty() {
+            eprintln!("Skipping day {}: no input in {}.", puzzle.day(), DATA_DIR);
+            continue;
+        }
+        for report in reports.iter_mut() {
+            key.verify(report);
+            if let Some(e) = &report.error {
+                problems.push(format!("{}: {}", report.input, e));
+            }
+            for part in report.parts.iter() {
+                match (&part.answer, &part.verdict) {
+                    (Err(e), _) => {
+                        problems.push(format!("{} puzzle {}: {}", report.input, part.part, e))
+                    }
+                    (Ok(_), Some(Verdict::Fail { .. })) => problems.push(format!(
+                        "{} puzzle {}: {}",
+                        report.input,
+                        part.part,
+                        part.verdict.as_ref().unwrap()
+                    )),
+                    (Ok(_), Some(Verdict::Pass)) => checked += 1,
+                    (Ok(_), _) => eprintln!(
+                        "No expected answer for {} puzzle {}.",
+                        report.input, part.part
+                    ),
+                }
+            }
+        }
+    }
+    assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+    eprintln!("Checked {} answers.", checked);
+}